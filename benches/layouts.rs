@@ -0,0 +1,63 @@
+use std::{hint::black_box, time::Instant};
+
+use spell_checker_bloom_filters::bloom_filters::{BloomFilter, Layout};
+
+const MAX_SIZE: usize = 10_000_000;
+const INSERTED_WORDS: usize = 10_000_000;
+const LOOKUPS: usize = 1_000_000;
+
+fn main() {
+    let words: Vec<String> = (0..INSERTED_WORDS)
+        .map(|idx| format!("word-{}", idx))
+        .collect();
+    let hits: Vec<String> = (0..LOOKUPS)
+        .map(|idx| format!("word-{}", (idx * 7_919) % INSERTED_WORDS))
+        .collect();
+    let misses: Vec<String> = (0..LOOKUPS).map(|idx| format!("miss-{}", idx)).collect();
+
+    for layout in [Layout::Flat, Layout::Partitioned, Layout::Blocked] {
+        let filter: BloomFilter = BloomFilter::builder()
+            .with_max_size(MAX_SIZE)
+            .with_layout(layout)
+            .build()
+            .unwrap();
+        filter.insert_many(words.iter().map(String::as_str));
+
+        report(&format!("{:?} (hits)", layout), LOOKUPS, || {
+            hits.iter()
+                .filter(|word| filter.contains(black_box(word)))
+                .count()
+        });
+        let false_positives = report(&format!("{:?} (misses)", layout), LOOKUPS, || {
+            misses
+                .iter()
+                .filter(|word| filter.contains(black_box(word)))
+                .count()
+        });
+
+        println!(
+            "{:<24} {:>8.5} measured {:>8.5} expected",
+            format!("{:?} (fp rate)", layout),
+            false_positives as f64 / LOOKUPS as f64,
+            filter.expected_false_positive_rate()
+        );
+    }
+}
+
+fn report(name: &str, operations: usize, run: impl Fn() -> usize) -> usize {
+    black_box(run());
+
+    let start = Instant::now();
+    let found = black_box(run());
+    let elapsed = start.elapsed();
+
+    println!(
+        "{:<24} {:>8.1} ns/op {:>12.0} ops/s ({} found)",
+        name,
+        elapsed.as_nanos() as f64 / operations as f64,
+        operations as f64 / elapsed.as_secs_f64(),
+        found
+    );
+
+    found
+}
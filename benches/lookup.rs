@@ -0,0 +1,105 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    hint::black_box,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Instant,
+};
+
+use spell_checker_bloom_filters::bloom_filters::BloomFilter;
+
+// Counting every allocation shows lookups hash their entries without touching the heap
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        // SAFETY: the layout is passed on unchanged to the system allocator
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        // SAFETY: the pointer was handed out by the system allocator with the same layout
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+const MAX_SIZE: usize = 10_000_000;
+const INSERTED_WORDS: usize = 1_000_000;
+const LOOKUPS: usize = 1_000_000;
+
+fn main() {
+    let words: Vec<String> = (0..INSERTED_WORDS)
+        .map(|idx| format!("word-{}", idx))
+        .collect();
+    let hits: Vec<String> = (0..LOOKUPS)
+        .map(|idx| format!("word-{}", (idx * 7_919) % INSERTED_WORDS))
+        .collect();
+    let misses: Vec<String> = (0..LOOKUPS).map(|idx| format!("miss-{}", idx)).collect();
+
+    report("insert", INSERTED_WORDS, || {
+        let filter = build_filter();
+        words.iter().for_each(|word| filter.insert(black_box(word)));
+        0
+    });
+    report("insert_many", INSERTED_WORDS, || {
+        let filter = build_filter();
+        filter.insert_many(words.iter().map(|word| black_box(word.as_str())));
+        0
+    });
+
+    let filter = build_filter();
+    filter.insert_many(words.iter().map(String::as_str));
+
+    report("contains (hits)", LOOKUPS, || {
+        hits.iter()
+            .filter(|word| filter.contains(black_box(word)))
+            .count()
+    });
+    report("contains (misses)", LOOKUPS, || {
+        misses
+            .iter()
+            .filter(|word| filter.contains(black_box(word)))
+            .count()
+    });
+    report("contains_many (hits)", LOOKUPS, || {
+        count_found(filter.contains_many(hits.iter().map(|word| black_box(word.as_str()))))
+    });
+    report("contains_many (misses)", LOOKUPS, || {
+        count_found(filter.contains_many(misses.iter().map(|word| black_box(word.as_str()))))
+    });
+}
+
+fn build_filter() -> BloomFilter {
+    BloomFilter::builder()
+        .with_max_size(MAX_SIZE)
+        .build()
+        .unwrap()
+}
+
+fn count_found(results: Vec<bool>) -> usize {
+    results.into_iter().filter(|&found| found).count()
+}
+
+fn report(name: &str, operations: usize, run: impl Fn() -> usize) {
+    black_box(run());
+
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let start = Instant::now();
+    let found = black_box(run());
+    let elapsed = start.elapsed();
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - allocations;
+
+    println!(
+        "{:<24} {:>8.1} ns/op {:>12.0} ops/s {:>8.4} allocs/op ({} found)",
+        name,
+        elapsed.as_nanos() as f64 / operations as f64,
+        operations as f64 / elapsed.as_secs_f64(),
+        allocations as f64 / operations as f64,
+        found
+    );
+}
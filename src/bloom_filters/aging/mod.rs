@@ -0,0 +1,321 @@
+use std::{
+    hash::Hash,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex, PoisonError,
+    },
+    time::{Duration, Instant},
+};
+
+pub use clock::{Clock, SystemClock};
+
+use super::{
+    builder::Builder,
+    configurations::{ConfigError, Configuration},
+    hashing::strategy::Hashing,
+    BloomFilter, BloomFilterError, Layout,
+};
+
+mod clock;
+
+#[derive(Clone, Copy)]
+pub enum Rotation {
+    Every(Duration),
+    EveryInsertions(usize),
+}
+
+struct Generations {
+    current: usize,
+    insertions_into_current: usize,
+}
+
+// AgingBloomFilter inserts into its newest generation and clears the oldest one on every rotation,
+// so an entry expires between num_of_generations - 1 and num_of_generations rotations after its insert
+// The last rotation is kept as nanoseconds since the filter started, so lookups check it without taking the lock
+pub struct AgingBloomFilter<T: ?Sized = str> {
+    filters: Vec<BloomFilter<T>>,
+    generations: Mutex<Generations>,
+    rotation: Rotation,
+    clock: Box<dyn Clock>,
+    started_at: Instant,
+    last_rotation: AtomicU64,
+}
+
+impl<T: Hash + ?Sized> AgingBloomFilter<T> {
+    pub(super) const DEFAULT_NUM_OF_GENERATIONS: usize = 3;
+
+    pub fn builder() -> Builder<Self> {
+        Builder::default()
+    }
+
+    pub(super) fn try_from(
+        configurations: Vec<Configuration>,
+        strategy: Option<Box<dyn Hashing>>,
+        rotation: Rotation,
+        clock: Box<dyn Clock>,
+    ) -> Result<Self, BloomFilterError> {
+        if configurations.len() < 2 {
+            return Err(BloomFilterError::Configuration(ConfigError::Validation(
+                String::from("Aging filters need at least 2 generations"),
+            )));
+        }
+
+        if matches!(rotation, Rotation::Every(interval) if interval.is_zero())
+            || matches!(rotation, Rotation::EveryInsertions(0))
+        {
+            return Err(BloomFilterError::Configuration(ConfigError::Validation(
+                String::from("Rotation boundary must be positive"),
+            )));
+        }
+
+        let mut configurations = configurations.into_iter();
+        let mut filters = Vec::with_capacity(configurations.len());
+
+        if let Some(configuration) = configurations.next() {
+            filters.push(BloomFilter::try_from(
+                configuration,
+                strategy,
+                Layout::Flat,
+            )?);
+        }
+
+        for configuration in configurations {
+            let strategy = filters[0]
+                .manager
+                .try_restore_strategy()
+                .map_err(String::from)
+                .map_err(BloomFilterError::Hashing)?;
+
+            filters.push(BloomFilter::try_from(
+                configuration,
+                Some(strategy),
+                Layout::Flat,
+            )?);
+        }
+
+        let generations = Mutex::new(Generations {
+            current: 0,
+            insertions_into_current: 0,
+        });
+
+        let started_at = clock.now();
+
+        Ok(Self {
+            filters,
+            generations,
+            rotation,
+            clock,
+            started_at,
+            last_rotation: AtomicU64::new(0),
+        })
+    }
+
+    pub fn insert(&self, entry: &T) {
+        let current = self.rotate_if_due(1);
+        self.filters[current].insert(entry);
+    }
+
+    pub fn contains(&self, entry: &T) -> bool {
+        if self.is_rotation_due() {
+            self.rotate_if_due(0);
+        }
+
+        self.filters.iter().any(|filter| filter.contains(entry))
+    }
+
+    pub fn get_num_of_generations(&self) -> usize {
+        self.filters.len()
+    }
+
+    // Lookups never count towards insertions, so only time can make a rotation due for them
+    fn is_rotation_due(&self) -> bool {
+        match self.rotation {
+            Rotation::Every(interval) => self.elapsed_since_last_rotation() >= interval,
+            Rotation::EveryInsertions(_) => false,
+        }
+    }
+
+    fn elapsed_since_last_rotation(&self) -> Duration {
+        self.clock
+            .now()
+            .saturating_duration_since(self.started_at)
+            .saturating_sub(Duration::from_nanos(
+                self.last_rotation.load(Ordering::Acquire),
+            ))
+    }
+
+    // Returns the generation to insert into once every rotation that fell due has happened
+    fn rotate_if_due(&self, insertions: usize) -> usize {
+        let mut generations = self
+            .generations
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        let num_of_rotations = match self.rotation {
+            Rotation::Every(interval) => {
+                let elapsed = self.elapsed_since_last_rotation().as_nanos();
+                let num_of_rotations = elapsed / interval.as_nanos();
+
+                // The last rotation moves by whole intervals, however many of them are skipped below
+                self.last_rotation.fetch_add(
+                    (elapsed - elapsed % interval.as_nanos()) as u64,
+                    Ordering::Release,
+                );
+
+                num_of_rotations.min(self.filters.len() as u128) as usize
+            }
+            Rotation::EveryInsertions(count) => {
+                usize::from(generations.insertions_into_current + insertions > count)
+            }
+        };
+
+        // Rotating more often than there are generations would only clear the same filters again
+        for _ in 0..num_of_rotations.min(self.filters.len()) {
+            generations.current = (generations.current + 1) % self.filters.len();
+            self.filters[generations.current].clear();
+        }
+
+        if num_of_rotations > 0 {
+            generations.insertions_into_current = 0;
+        }
+
+        generations.insertions_into_current += insertions;
+
+        generations.current
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        sync::{
+            atomic::{AtomicU64, Ordering},
+            Arc,
+        },
+        time::{Duration, Instant},
+    };
+
+    use super::{AgingBloomFilter, Clock, Rotation};
+
+    struct ManualClock {
+        start: Instant,
+        elapsed_secs: Arc<AtomicU64>,
+    }
+
+    impl Clock for ManualClock {
+        fn now(&self) -> Instant {
+            self.start + Duration::from_secs(self.elapsed_secs.load(Ordering::Relaxed))
+        }
+    }
+
+    fn build_filter_with_manual_clock() -> (AgingBloomFilter, Arc<AtomicU64>) {
+        let elapsed_secs = Arc::new(AtomicU64::new(0));
+
+        let filter = AgingBloomFilter::builder()
+            .with_max_size(1_000)
+            .with_num_of_generations(3)
+            .with_rotation(Rotation::Every(Duration::from_secs(3_600)))
+            .with_clock(Box::new(ManualClock {
+                start: Instant::now(),
+                elapsed_secs: elapsed_secs.clone(),
+            }))
+            .build()
+            .unwrap();
+
+        (filter, elapsed_secs)
+    }
+
+    #[test]
+    fn should_keep_entries_until_every_generation_rotated_past_them() {
+        let (filter, elapsed_secs) = build_filter_with_manual_clock();
+
+        filter.insert("hello");
+
+        elapsed_secs.store(3_600, Ordering::Relaxed);
+        filter.insert("world");
+        assert!(filter.contains("hello"));
+
+        elapsed_secs.store(7_200, Ordering::Relaxed);
+        assert!(filter.contains("hello"));
+        assert!(filter.contains("world"));
+
+        elapsed_secs.store(10_800, Ordering::Relaxed);
+        assert!(!filter.contains("hello"));
+        assert!(filter.contains("world"));
+
+        elapsed_secs.store(14_400, Ordering::Relaxed);
+        assert!(!filter.contains("world"));
+    }
+
+    #[test]
+    fn should_expire_everything_after_long_pause() {
+        let (filter, elapsed_secs) = build_filter_with_manual_clock();
+
+        filter.insert("hello");
+        elapsed_secs.store(100 * 3_600, Ordering::Relaxed);
+
+        assert!(!filter.contains("hello"));
+
+        filter.insert("world");
+        assert!(filter.contains("world"));
+    }
+
+    #[test]
+    fn should_expire_everything_after_idle_period_of_many_short_intervals() {
+        let elapsed_secs = Arc::new(AtomicU64::new(0));
+
+        let filter: AgingBloomFilter = AgingBloomFilter::builder()
+            .with_max_size(1_000)
+            .with_rotation(Rotation::Every(Duration::from_nanos(1)))
+            .with_clock(Box::new(ManualClock {
+                start: Instant::now(),
+                elapsed_secs: elapsed_secs.clone(),
+            }))
+            .build()
+            .unwrap();
+
+        filter.insert("hello");
+        // 2^23 seconds are a multiple of 2^32 nanoseconds, a rotation count cut to 32 bits would be 0
+        elapsed_secs.store(1 << 23, Ordering::Relaxed);
+
+        assert!(!filter.contains("hello"));
+    }
+
+    #[test]
+    fn should_rotate_after_given_number_of_insertions() {
+        let filter: AgingBloomFilter<u64> = AgingBloomFilter::builder()
+            .with_max_size(1_000)
+            .with_num_of_generations(2)
+            .with_rotation(Rotation::EveryInsertions(100))
+            .build()
+            .unwrap();
+
+        (0..100).for_each(|entry| filter.insert(&entry));
+        assert!((0..100).all(|entry| filter.contains(&entry)));
+
+        (100..200).for_each(|entry| filter.insert(&entry));
+        assert!((0..200).all(|entry| filter.contains(&entry)));
+
+        filter.insert(&200);
+        assert!((100..201).all(|entry| filter.contains(&entry)));
+        assert!((0..100).filter(|entry| filter.contains(entry)).count() < 5);
+    }
+
+    #[test]
+    fn should_return_err_when_generations_or_rotation_are_invalid() {
+        let maybe_filter = AgingBloomFilter::<str>::builder()
+            .with_num_of_generations(1)
+            .build();
+        assert!(maybe_filter.is_err());
+
+        let maybe_filter = AgingBloomFilter::<str>::builder()
+            .with_rotation(Rotation::Every(Duration::ZERO))
+            .build();
+        assert!(maybe_filter.is_err());
+
+        let maybe_filter = AgingBloomFilter::<str>::builder()
+            .with_rotation(Rotation::EveryInsertions(0))
+            .build();
+        assert!(maybe_filter.is_err());
+    }
+}
@@ -1,42 +1,345 @@
-use super::{hashing::strategy::Hashing, BloomFilter, BloomFilterError};
+use std::{hash::Hash, marker::PhantomData};
 
-#[derive(Default)]
-pub struct Builder {
+use super::{
+    aging::{AgingBloomFilter, Clock, Rotation, SystemClock},
+    configurations::{ConfigError, Configuration, Limits, Sizing},
+    hashing::strategy::{self, Hashing},
+    BloomFilter, BloomFilterError, CountingBloomFilter, Layout, Parameters, ScalableBloomFilter,
+    StableBloomFilter, SupportedFloatingPointType,
+};
+
+pub struct Builder<F = BloomFilter> {
     max_size: Option<usize>,
-    max_tolerance: Option<f32>,
+    sizing: Option<Sizing>,
+    num_of_hash_funcs: Option<usize>,
     strategy: Option<Box<dyn Hashing>>,
+    limits: Limits,
+    max_cell_value: Option<u8>,
+    num_of_generations: Option<usize>,
+    rotation: Option<Rotation>,
+    clock: Option<Box<dyn Clock>>,
+    layout: Option<Layout>,
+    filter: PhantomData<F>,
+}
+
+impl<F> Default for Builder<F> {
+    fn default() -> Self {
+        Self {
+            max_size: None,
+            sizing: None,
+            num_of_hash_funcs: None,
+            strategy: None,
+            limits: Limits::default(),
+            max_cell_value: None,
+            num_of_generations: None,
+            rotation: None,
+            clock: None,
+            layout: None,
+            filter: PhantomData,
+        }
+    }
 }
 
-impl Builder {
-    pub fn build(self) -> Result<BloomFilter, BloomFilterError> {
+impl<T: Hash + ?Sized> Builder<BloomFilter<T>> {
+    pub fn build(self) -> Result<BloomFilter<T>, BloomFilterError> {
+        let configuration = self.configure()?;
         BloomFilter::try_from(
+            configuration,
+            self.strategy,
+            self.layout.unwrap_or_default(),
+        )
+    }
+
+    pub fn with_layout(self, layout: Layout) -> Self {
+        Self {
+            max_size: self.max_size,
+            sizing: self.sizing,
+            num_of_hash_funcs: self.num_of_hash_funcs,
+            strategy: self.strategy,
+            limits: self.limits,
+            max_cell_value: self.max_cell_value,
+            num_of_generations: self.num_of_generations,
+            rotation: self.rotation,
+            clock: self.clock,
+            layout: Some(layout),
+            filter: PhantomData,
+        }
+    }
+}
+
+impl<T: Hash + ?Sized> Builder<CountingBloomFilter<T>> {
+    pub fn build(self) -> Result<CountingBloomFilter<T>, BloomFilterError> {
+        let configuration = self.configure()?;
+        CountingBloomFilter::try_from(configuration, self.strategy)
+    }
+}
+
+impl<T: Hash + ?Sized> Builder<StableBloomFilter<T>> {
+    // Tolerance sizing targets the false positive rate the filter converges to, not the one of max_size entries
+    pub fn build(self) -> Result<StableBloomFilter<T>, BloomFilterError> {
+        let configuration = self.configure()?;
+        StableBloomFilter::try_from(
+            configuration,
+            self.strategy,
+            self.max_cell_value
+                .unwrap_or(StableBloomFilter::<T>::DEFAULT_MAX_CELL_VALUE),
+        )
+    }
+
+    pub fn with_max_cell_value(self, max_cell_value: u8) -> Self {
+        Self {
+            max_size: self.max_size,
+            sizing: self.sizing,
+            num_of_hash_funcs: self.num_of_hash_funcs,
+            strategy: self.strategy,
+            limits: self.limits,
+            max_cell_value: Some(max_cell_value),
+            num_of_generations: self.num_of_generations,
+            rotation: self.rotation,
+            clock: self.clock,
+            layout: self.layout,
+            filter: PhantomData,
+        }
+    }
+}
+
+impl<T: Hash + ?Sized> Builder<AgingBloomFilter<T>> {
+    // Every generation is sized like a standalone filter, the default rotation happens once one is full
+    pub fn build(self) -> Result<AgingBloomFilter<T>, BloomFilterError> {
+        let configurations = (0..self
+            .num_of_generations
+            .unwrap_or(AgingBloomFilter::<T>::DEFAULT_NUM_OF_GENERATIONS))
+            .map(|_| self.configure())
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let rotation = self.rotation.unwrap_or(Rotation::EveryInsertions(
             self.max_size.unwrap_or(1_000_000),
-            self.max_tolerance.unwrap_or(0.01),
+        ));
+
+        AgingBloomFilter::try_from(
+            configurations,
             self.strategy,
+            rotation,
+            self.clock.unwrap_or_else(|| Box::new(SystemClock)),
         )
     }
 
+    pub fn with_num_of_generations(self, num_of_generations: usize) -> Self {
+        Self {
+            max_size: self.max_size,
+            sizing: self.sizing,
+            num_of_hash_funcs: self.num_of_hash_funcs,
+            strategy: self.strategy,
+            limits: self.limits,
+            max_cell_value: self.max_cell_value,
+            num_of_generations: Some(num_of_generations),
+            rotation: self.rotation,
+            clock: self.clock,
+            layout: self.layout,
+            filter: PhantomData,
+        }
+    }
+
+    pub fn with_rotation(self, rotation: Rotation) -> Self {
+        Self {
+            max_size: self.max_size,
+            sizing: self.sizing,
+            num_of_hash_funcs: self.num_of_hash_funcs,
+            strategy: self.strategy,
+            limits: self.limits,
+            max_cell_value: self.max_cell_value,
+            num_of_generations: self.num_of_generations,
+            rotation: Some(rotation),
+            clock: self.clock,
+            layout: self.layout,
+            filter: PhantomData,
+        }
+    }
+
+    pub fn with_clock(self, clock: Box<dyn Clock>) -> Self {
+        Self {
+            max_size: self.max_size,
+            sizing: self.sizing,
+            num_of_hash_funcs: self.num_of_hash_funcs,
+            strategy: self.strategy,
+            limits: self.limits,
+            max_cell_value: self.max_cell_value,
+            num_of_generations: self.num_of_generations,
+            rotation: self.rotation,
+            clock: Some(clock),
+            layout: self.layout,
+            filter: PhantomData,
+        }
+    }
+}
+
+impl<T: Hash + ?Sized> Builder<ScalableBloomFilter<T>> {
+    // Layers derive their size from a tightening tolerance, so no other sizing can carry over to them
+    pub fn build(self) -> Result<ScalableBloomFilter<T>, BloomFilterError> {
+        let max_tolerance = match self.sizing {
+            None if self.num_of_hash_funcs.is_none() => 0.01,
+            Some(Sizing::Tolerance(max_tolerance)) if self.num_of_hash_funcs.is_none() => {
+                max_tolerance
+            }
+            _ => {
+                return Err(BloomFilterError::Configuration(ConfigError::Validation(
+                    String::from("Scalable filters can only be sized by tolerance"),
+                )))
+            }
+        };
+
+        ScalableBloomFilter::try_from(
+            self.max_size.unwrap_or(1_000_000),
+            max_tolerance,
+            self.strategy,
+            &self.limits,
+        )
+    }
+}
+
+impl<F> Builder<F> {
     pub fn with_max_size(self, max_size: usize) -> Self {
         Self {
             max_size: Some(max_size),
-            max_tolerance: self.max_tolerance,
+            sizing: self.sizing,
+            num_of_hash_funcs: self.num_of_hash_funcs,
             strategy: self.strategy,
+            limits: self.limits,
+            max_cell_value: self.max_cell_value,
+            num_of_generations: self.num_of_generations,
+            rotation: self.rotation,
+            clock: self.clock,
+            layout: self.layout,
+            filter: PhantomData,
         }
     }
 
-    pub fn with_max_tolerance(self, max_tolerance: f32) -> Self {
+    pub fn with_max_tolerance(self, max_tolerance: SupportedFloatingPointType) -> Self {
+        self.with_sizing(Sizing::Tolerance(max_tolerance))
+    }
+
+    pub fn with_total_bits(self, total_bits: usize) -> Self {
+        self.with_sizing(Sizing::TotalBits(total_bits))
+    }
+
+    pub fn with_bits_per_entry(self, bits_per_entry: SupportedFloatingPointType) -> Self {
+        self.with_sizing(Sizing::BitsPerEntry(bits_per_entry))
+    }
+
+    pub fn with_memory_budget(self, bytes: usize) -> Self {
+        self.with_sizing(Sizing::MemoryBudget(bytes))
+    }
+
+    pub fn with_num_of_hash_funcs(self, num_of_hash_funcs: usize) -> Self {
         Self {
             max_size: self.max_size,
-            max_tolerance: Some(max_tolerance),
+            sizing: self.sizing,
+            num_of_hash_funcs: Some(num_of_hash_funcs),
             strategy: self.strategy,
+            limits: self.limits,
+            max_cell_value: self.max_cell_value,
+            num_of_generations: self.num_of_generations,
+            rotation: self.rotation,
+            clock: self.clock,
+            layout: self.layout,
+            filter: PhantomData,
         }
     }
 
     pub fn with_strategy(self, strategy: Box<dyn Hashing>) -> Self {
         Self {
             max_size: self.max_size,
-            max_tolerance: self.max_tolerance,
+            sizing: self.sizing,
+            num_of_hash_funcs: self.num_of_hash_funcs,
             strategy: Some(strategy),
+            limits: self.limits,
+            max_cell_value: self.max_cell_value,
+            num_of_generations: self.num_of_generations,
+            rotation: self.rotation,
+            clock: self.clock,
+            layout: self.layout,
+            filter: PhantomData,
+        }
+    }
+
+    pub fn with_capacity_limit(self, max_capacity: usize) -> Self {
+        Self {
+            max_size: self.max_size,
+            sizing: self.sizing,
+            num_of_hash_funcs: self.num_of_hash_funcs,
+            strategy: self.strategy,
+            limits: self.limits.with_max_capacity(max_capacity),
+            max_cell_value: self.max_cell_value,
+            num_of_generations: self.num_of_generations,
+            rotation: self.rotation,
+            clock: self.clock,
+            layout: self.layout,
+            filter: PhantomData,
+        }
+    }
+
+    pub fn with_total_bits_limit(self, max_total_bits: u64) -> Self {
+        Self {
+            max_size: self.max_size,
+            sizing: self.sizing,
+            num_of_hash_funcs: self.num_of_hash_funcs,
+            strategy: self.strategy,
+            limits: self.limits.with_max_total_bits(max_total_bits),
+            max_cell_value: self.max_cell_value,
+            num_of_generations: self.num_of_generations,
+            rotation: self.rotation,
+            clock: self.clock,
+            layout: self.layout,
+            filter: PhantomData,
         }
     }
+
+    // Parameters replace the max size, the sizing and the strategy chosen before, like calling their methods would
+    pub fn with_parameters(self, parameters: &Parameters) -> Self {
+        Self {
+            max_size: Some(parameters.get_max_size()),
+            sizing: Some(Sizing::Tolerance(parameters.get_max_tolerance())),
+            num_of_hash_funcs: self.num_of_hash_funcs,
+            strategy: Some(
+                strategy::from_identifier(
+                    parameters.get_strategy_identifier(),
+                    parameters.get_seed(),
+                )
+                .expect("Parameters only hold strategies that can be restored"),
+            ),
+            limits: self.limits,
+            max_cell_value: self.max_cell_value,
+            num_of_generations: self.num_of_generations,
+            rotation: self.rotation,
+            clock: self.clock,
+            layout: self.layout,
+            filter: PhantomData,
+        }
+    }
+
+    fn with_sizing(self, sizing: Sizing) -> Self {
+        Self {
+            max_size: self.max_size,
+            sizing: Some(sizing),
+            num_of_hash_funcs: self.num_of_hash_funcs,
+            strategy: self.strategy,
+            limits: self.limits,
+            max_cell_value: self.max_cell_value,
+            num_of_generations: self.num_of_generations,
+            rotation: self.rotation,
+            clock: self.clock,
+            layout: self.layout,
+            filter: PhantomData,
+        }
+    }
+
+    fn configure(&self) -> Result<Configuration, BloomFilterError> {
+        Configuration::try_from_sizing(
+            self.max_size.unwrap_or(1_000_000),
+            self.sizing.unwrap_or(Sizing::Tolerance(0.01)),
+            self.num_of_hash_funcs,
+            &self.limits,
+        )
+        .map_err(BloomFilterError::Configuration)
+    }
 }
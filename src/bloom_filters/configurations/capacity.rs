@@ -1,27 +1,15 @@
-use std::cell::RefCell;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 pub(crate) struct Capacity {
     expected_capacity: usize,
-    actual_size: RefCell<usize>,
+    actual_size: AtomicUsize,
 }
 
 impl Capacity {
-    const MAXIMUM_CAPACITY_ALLOWED: usize = 10_000_000;
+    pub(super) const DEFAULT_MAXIMUM_CAPACITY_ALLOWED: usize = 10_000_000;
 
-    pub(super) fn get_capacity(&self) -> usize {
-        self.expected_capacity
-    }
-
-    pub(super) fn increase_size_by_one(&self) {
-        *self.actual_size.borrow_mut() += 1;
-    }
-}
-
-impl TryFrom<usize> for Capacity {
-    type Error = &'static str;
-
-    fn try_from(value: usize) -> Result<Self, Self::Error> {
-        if value > Self::MAXIMUM_CAPACITY_ALLOWED {
+    pub(super) fn try_from(value: usize, maximum: usize) -> Result<Self, &'static str> {
+        if value > maximum {
             return Err("Not enough capacity for storage");
         }
 
@@ -31,9 +19,33 @@ impl TryFrom<usize> for Capacity {
 
         Ok(Self {
             expected_capacity: value,
-            actual_size: RefCell::new(0),
+            actual_size: AtomicUsize::new(0),
         })
     }
+
+    pub(super) fn get_capacity(&self) -> usize {
+        self.expected_capacity
+    }
+
+    pub(super) fn get_size(&self) -> usize {
+        self.actual_size.load(Ordering::Relaxed)
+    }
+
+    pub(super) fn increase_size_by_one(&self) {
+        self.actual_size.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(super) fn decrease_size_by_one(&self) {
+        let _ = self
+            .actual_size
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |size| {
+                size.checked_sub(1)
+            });
+    }
+
+    pub(super) fn restore_size(&self, size: usize) {
+        self.actual_size.store(size, Ordering::Relaxed);
+    }
 }
 
 #[cfg(test)]
@@ -44,12 +56,15 @@ mod test {
     fn should_return_err_when_capacity_is_invalid() {
         let expected_capacities = vec![
             0,
-            Capacity::MAXIMUM_CAPACITY_ALLOWED + 1,
-            Capacity::MAXIMUM_CAPACITY_ALLOWED + 100,
+            Capacity::DEFAULT_MAXIMUM_CAPACITY_ALLOWED + 1,
+            Capacity::DEFAULT_MAXIMUM_CAPACITY_ALLOWED + 100,
         ];
 
         for expected_capacity in expected_capacities {
-            let maybe_capacity = Capacity::try_from(expected_capacity);
+            let maybe_capacity = Capacity::try_from(
+                expected_capacity,
+                Capacity::DEFAULT_MAXIMUM_CAPACITY_ALLOWED,
+            );
 
             assert!(maybe_capacity.is_err());
         }
@@ -60,19 +75,22 @@ mod test {
         let expected_capacities = vec![
             1,
             100,
-            Capacity::MAXIMUM_CAPACITY_ALLOWED / 2,
-            Capacity::MAXIMUM_CAPACITY_ALLOWED,
+            Capacity::DEFAULT_MAXIMUM_CAPACITY_ALLOWED / 2,
+            Capacity::DEFAULT_MAXIMUM_CAPACITY_ALLOWED,
         ];
 
         for expected_capacity in expected_capacities {
-            let maybe_capacity = Capacity::try_from(expected_capacity);
+            let maybe_capacity = Capacity::try_from(
+                expected_capacity,
+                Capacity::DEFAULT_MAXIMUM_CAPACITY_ALLOWED,
+            );
 
             assert!(maybe_capacity.is_ok());
 
             let capacity = maybe_capacity.unwrap();
 
             assert_eq!(capacity.expected_capacity, expected_capacity);
-            assert_eq!(*capacity.actual_size.borrow(), 0);
+            assert_eq!(capacity.get_size(), 0);
         }
     }
 
@@ -81,25 +99,50 @@ mod test {
         let expected_capacities = vec![
             1,
             100,
-            Capacity::MAXIMUM_CAPACITY_ALLOWED / 2,
-            Capacity::MAXIMUM_CAPACITY_ALLOWED,
+            Capacity::DEFAULT_MAXIMUM_CAPACITY_ALLOWED / 2,
+            Capacity::DEFAULT_MAXIMUM_CAPACITY_ALLOWED,
         ];
 
         for expected_capacity in expected_capacities {
-            let maybe_capacity = Capacity::try_from(expected_capacity);
+            let maybe_capacity = Capacity::try_from(
+                expected_capacity,
+                Capacity::DEFAULT_MAXIMUM_CAPACITY_ALLOWED,
+            );
 
             assert!(maybe_capacity.is_ok());
 
             let capacity = maybe_capacity.unwrap();
 
             assert_eq!(capacity.expected_capacity, expected_capacity);
-            assert_eq!(*capacity.actual_size.borrow(), 0);
+            assert_eq!(capacity.get_size(), 0);
 
             capacity.increase_size_by_one();
-            assert_eq!(*capacity.actual_size.borrow(), 1);
+            assert_eq!(capacity.get_size(), 1);
 
             capacity.increase_size_by_one();
-            assert_eq!(*capacity.actual_size.borrow(), 2);
+            assert_eq!(capacity.get_size(), 2);
         }
     }
+
+    #[test]
+    fn should_accept_capacity_up_to_given_maximum() {
+        let pwned_passwords_capacity = 600_000_000;
+
+        assert!(Capacity::try_from(pwned_passwords_capacity, pwned_passwords_capacity).is_ok());
+        assert!(
+            Capacity::try_from(pwned_passwords_capacity + 1, pwned_passwords_capacity).is_err()
+        );
+    }
+
+    #[test]
+    fn should_have_size_decreased_by_one_without_underflow() {
+        let capacity = Capacity::try_from(100, 100).unwrap();
+
+        capacity.increase_size_by_one();
+        capacity.decrease_size_by_one();
+        assert_eq!(capacity.get_size(), 0);
+
+        capacity.decrease_size_by_one();
+        assert_eq!(capacity.get_size(), 0);
+    }
 }
@@ -0,0 +1,48 @@
+use super::{capacity::Capacity, storage_bits::StorageBits};
+
+#[derive(Clone, Copy)]
+pub(crate) struct Limits {
+    max_capacity: usize,
+    max_total_bits: u64,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            max_capacity: Capacity::DEFAULT_MAXIMUM_CAPACITY_ALLOWED,
+            max_total_bits: StorageBits::DEFAULT_MAXIMUM_BITS_ALLOWED_IN_STORAGE,
+        }
+    }
+}
+
+impl Limits {
+    // Filters whose storage is already at hand only need to describe it, so nothing bounds them
+    pub(crate) fn unbounded() -> Self {
+        Self {
+            max_capacity: usize::MAX,
+            max_total_bits: u64::MAX,
+        }
+    }
+
+    pub(crate) fn with_max_capacity(self, max_capacity: usize) -> Self {
+        Self {
+            max_capacity,
+            max_total_bits: self.max_total_bits,
+        }
+    }
+
+    pub(crate) fn with_max_total_bits(self, max_total_bits: u64) -> Self {
+        Self {
+            max_capacity: self.max_capacity,
+            max_total_bits,
+        }
+    }
+
+    pub(super) fn get_max_capacity(&self) -> usize {
+        self.max_capacity
+    }
+
+    pub(crate) fn get_max_total_bits(&self) -> u64 {
+        self.max_total_bits
+    }
+}
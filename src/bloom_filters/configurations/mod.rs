@@ -1,10 +1,18 @@
+use std::fmt;
+
 use capacity::Capacity;
+pub(crate) use limits::Limits;
 use storage_bits::StorageBits;
-use tolerance::Tolerance;
+pub(crate) use tolerance::Tolerance;
+use utility::{
+    calculate_false_positive_rate, calculate_optimal_number_of_hash_functions,
+    MAXIMUM_NUMBER_OF_HASH_FUNCTIONS,
+};
 
 use super::SupportedFloatingPointType;
 
 mod capacity;
+mod limits;
 mod storage_bits;
 mod tolerance;
 mod utility;
@@ -12,6 +20,10 @@ mod utility;
 pub(crate) trait Configurable {
     fn get_max_tolerance(&self) -> SupportedFloatingPointType;
     fn get_total_bits(&self) -> usize;
+    fn get_max_size(&self) -> usize;
+    fn get_num_of_hash_funcs(&self) -> usize;
+    fn get_unique_entry_count(&self) -> usize;
+    fn get_expected_false_positive_rate(&self) -> SupportedFloatingPointType;
 }
 
 #[derive(Debug)]
@@ -20,41 +32,193 @@ pub enum ConfigError {
     Dependency(String),
 }
 
+#[derive(Clone, Copy)]
+pub(crate) enum Sizing {
+    Tolerance(SupportedFloatingPointType),
+    TotalBits(usize),
+    BitsPerEntry(SupportedFloatingPointType),
+    MemoryBudget(usize),
+}
+
 pub(crate) struct Configuration {
     tolerance: Tolerance,
     capacity: Capacity,
     storage_bits: StorageBits,
+    num_of_hash_funcs: usize,
+    limits: Limits,
 }
 
 impl Configuration {
     pub(crate) fn try_from(
         max_tolerance: SupportedFloatingPointType,
         max_size: usize,
+        limits: &Limits,
     ) -> Result<Self, ConfigError> {
-        let (capacity, tolerance) = match (
-            Capacity::try_from(max_size),
-            Tolerance::try_from(max_tolerance),
-        ) {
-            (Ok(capacity), Ok(tolerance)) => (capacity, tolerance),
-            (Err(message), _) => return Err(ConfigError::Dependency(String::from(message))),
-            (_, Err(message)) => return Err(ConfigError::Validation(String::from(message))),
-        };
+        Self::try_from_sizing(max_size, Sizing::Tolerance(max_tolerance), None, limits)
+    }
+
+    // Every sizing but the tolerance one ends up with the false positive rate its bits and hash functions allow
+    pub(crate) fn try_from_sizing(
+        max_size: usize,
+        sizing: Sizing,
+        num_of_hash_funcs: Option<usize>,
+        limits: &Limits,
+    ) -> Result<Self, ConfigError> {
+        let capacity = Capacity::try_from(max_size, limits.get_max_capacity())
+            .map_err(|message| ConfigError::Dependency(String::from(message)))?;
+
+        if let Sizing::Tolerance(max_tolerance) = sizing {
+            let tolerance = Tolerance::try_from(max_tolerance)
+                .map_err(|message| ConfigError::Validation(String::from(message)))?;
+
+            let storage_bits =
+                StorageBits::try_from(&capacity, &tolerance, limits.get_max_total_bits())
+                    .map_err(|message| ConfigError::Dependency(String::from(message)))?;
+
+            let num_of_hash_funcs = num_of_hash_funcs.unwrap_or_else(|| {
+                calculate_optimal_number_of_hash_functions(max_size, *storage_bits)
+            });
 
-        let storage_bits = match StorageBits::try_from(&capacity, &tolerance) {
-            Ok(value) => value,
-            Err(message) => return Err(ConfigError::Dependency(String::from(message))),
+            return Self::try_from_parts(
+                tolerance,
+                capacity,
+                storage_bits,
+                num_of_hash_funcs,
+                limits,
+            );
+        }
+
+        let total_bits = match sizing {
+            Sizing::TotalBits(total_bits) => total_bits,
+            Sizing::BitsPerEntry(bits_per_entry) if bits_per_entry > 0.0 => {
+                (max_size as SupportedFloatingPointType * bits_per_entry).ceil() as usize
+            }
+            Sizing::BitsPerEntry(_) => {
+                return Err(ConfigError::Validation(String::from(
+                    "Bits per entry must be positive",
+                )))
+            }
+            Sizing::MemoryBudget(bytes) => bytes.saturating_mul(8),
+            Sizing::Tolerance(_) => unreachable!("Tolerance sizing is handled above"),
         };
 
+        let storage_bits =
+            StorageBits::try_from_total_bits(total_bits, limits.get_max_total_bits())
+                .map_err(|message| ConfigError::Dependency(String::from(message)))?;
+
+        let num_of_hash_funcs = num_of_hash_funcs
+            .unwrap_or_else(|| calculate_optimal_number_of_hash_functions(max_size, total_bits));
+
+        // Far more bits than needed make the rate underflow, which is still a valid tolerance
+        let tolerance = Tolerance::try_from(
+            calculate_false_positive_rate(max_size, total_bits, num_of_hash_funcs)
+                .max(SupportedFloatingPointType::MIN_POSITIVE),
+        )
+        .map_err(|_| {
+            ConfigError::Dependency(String::from(
+                "Not enough bits to hold that many entries with any tolerance",
+            ))
+        })?;
+
+        Self::try_from_parts(tolerance, capacity, storage_bits, num_of_hash_funcs, limits)
+    }
+
+    pub(crate) fn try_restore(
+        max_tolerance: SupportedFloatingPointType,
+        max_size: usize,
+        total_bits: usize,
+        num_of_hash_funcs: usize,
+        limits: &Limits,
+    ) -> Result<Self, ConfigError> {
+        let tolerance = Tolerance::try_from(max_tolerance)
+            .map_err(|message| ConfigError::Validation(String::from(message)))?;
+
+        let capacity = Capacity::try_from(max_size, limits.get_max_capacity())
+            .map_err(|message| ConfigError::Dependency(String::from(message)))?;
+
+        let storage_bits =
+            StorageBits::try_from_total_bits(total_bits, limits.get_max_total_bits())
+                .map_err(|message| ConfigError::Dependency(String::from(message)))?;
+
+        Self::try_from_parts(tolerance, capacity, storage_bits, num_of_hash_funcs, limits)
+    }
+
+    pub(crate) fn try_clone_empty(&self) -> Result<Self, ConfigError> {
+        Self::try_restore(
+            self.get_max_tolerance(),
+            self.get_max_size(),
+            self.get_total_bits(),
+            self.get_num_of_hash_funcs(),
+            &self.limits,
+        )
+    }
+
+    fn try_from_parts(
+        tolerance: Tolerance,
+        capacity: Capacity,
+        storage_bits: StorageBits,
+        num_of_hash_funcs: usize,
+        limits: &Limits,
+    ) -> Result<Self, ConfigError> {
+        if num_of_hash_funcs == 0 {
+            return Err(ConfigError::Validation(String::from(
+                "Number of hash functions must be positive",
+            )));
+        }
+
+        // Every lookup probes each hash function, so a forged count cannot be allowed to stall it
+        if num_of_hash_funcs > MAXIMUM_NUMBER_OF_HASH_FUNCTIONS {
+            return Err(ConfigError::Validation(String::from(
+                "Too many hash functions",
+            )));
+        }
+
         Ok(Self {
             tolerance,
             capacity,
             storage_bits,
+            num_of_hash_funcs,
+            limits: *limits,
         })
     }
 
+    // Folding keeps every parameter but the storage, which shrinks by the given factor
+    pub(crate) fn try_fold(&self, factor: usize) -> Result<Self, ConfigError> {
+        if factor < 2 || !factor.is_power_of_two() || !self.get_total_bits().is_multiple_of(factor)
+        {
+            return Err(ConfigError::Validation(String::from(
+                "Fold factor must be a power of two dividing total bits",
+            )));
+        }
+
+        let configuration = Self::try_restore(
+            self.get_max_tolerance(),
+            self.get_max_size(),
+            self.get_total_bits() / factor,
+            self.get_num_of_hash_funcs(),
+            &self.limits,
+        )?;
+
+        configuration.restore_unique_entry_count(self.get_unique_entry_count());
+
+        Ok(configuration)
+    }
+
+    pub(crate) fn get_limits(&self) -> &Limits {
+        &self.limits
+    }
+
     pub(crate) fn increase_unique_entry_count(&self) {
         self.capacity.increase_size_by_one()
     }
+
+    pub(crate) fn decrease_unique_entry_count(&self) {
+        self.capacity.decrease_size_by_one()
+    }
+
+    pub(crate) fn restore_unique_entry_count(&self, count: usize) {
+        self.capacity.restore_size(count)
+    }
 }
 
 impl Configurable for Configuration {
@@ -65,4 +229,208 @@ impl Configurable for Configuration {
     fn get_total_bits(&self) -> usize {
         *self.storage_bits
     }
+
+    fn get_max_size(&self) -> usize {
+        self.capacity.get_capacity()
+    }
+
+    fn get_num_of_hash_funcs(&self) -> usize {
+        self.num_of_hash_funcs
+    }
+
+    fn get_unique_entry_count(&self) -> usize {
+        self.capacity.get_size()
+    }
+
+    fn get_expected_false_positive_rate(&self) -> SupportedFloatingPointType {
+        calculate_false_positive_rate(
+            self.get_max_size(),
+            self.get_total_bits(),
+            self.get_num_of_hash_funcs(),
+        )
+    }
+}
+
+impl fmt::Debug for Configuration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Configuration")
+            .field("max_tolerance", &self.get_max_tolerance())
+            .field("max_size", &self.get_max_size())
+            .field("total_bits", &self.get_total_bits())
+            .field("num_of_hash_funcs", &self.get_num_of_hash_funcs())
+            .field("unique_entry_count", &self.get_unique_entry_count())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        utility::MAXIMUM_NUMBER_OF_HASH_FUNCTIONS, Configurable, Configuration, Limits, Sizing,
+    };
+
+    #[test]
+    fn should_size_by_tolerance() {
+        let configuration = Configuration::try_from(0.01, 1_000_000, &Limits::default()).unwrap();
+
+        assert_eq!(configuration.get_total_bits(), 9_585_059);
+        assert_eq!(configuration.get_num_of_hash_funcs(), 7);
+        assert_eq!(configuration.get_max_tolerance(), 0.01);
+    }
+
+    #[test]
+    fn should_size_by_very_small_tolerance() {
+        let configuration = Configuration::try_from(1e-12, 1_000_000, &Limits::default()).unwrap();
+
+        assert_eq!(configuration.get_total_bits(), 57_510_351);
+        assert_eq!(configuration.get_num_of_hash_funcs(), 40);
+        assert!(configuration.get_expected_false_positive_rate() < 1.01e-12);
+    }
+
+    #[test]
+    fn should_size_by_total_bits_and_hash_functions() {
+        let configuration = Configuration::try_from_sizing(
+            1_000,
+            Sizing::TotalBits(8_000),
+            Some(3),
+            &Limits::default(),
+        )
+        .unwrap();
+
+        assert_eq!(configuration.get_total_bits(), 8_000);
+        assert_eq!(configuration.get_num_of_hash_funcs(), 3);
+        assert_eq!(
+            configuration.get_max_tolerance(),
+            configuration.get_expected_false_positive_rate()
+        );
+    }
+
+    #[test]
+    fn should_pick_optimal_hash_functions_for_total_bits() {
+        let configuration = Configuration::try_from_sizing(
+            1_000,
+            Sizing::TotalBits(8_000),
+            None,
+            &Limits::default(),
+        )
+        .unwrap();
+
+        assert_eq!(configuration.get_num_of_hash_funcs(), 6);
+        assert!((configuration.get_max_tolerance() - 0.0216).abs() < 0.0005);
+    }
+
+    #[test]
+    fn should_size_by_bits_per_entry() {
+        let configuration = Configuration::try_from_sizing(
+            1_000,
+            Sizing::BitsPerEntry(9.6),
+            None,
+            &Limits::default(),
+        )
+        .unwrap();
+
+        assert_eq!(configuration.get_total_bits(), 9_600);
+        assert_eq!(configuration.get_num_of_hash_funcs(), 7);
+        assert!(configuration.get_max_tolerance() < 0.011);
+    }
+
+    #[test]
+    fn should_size_by_memory_budget() {
+        let configuration = Configuration::try_from_sizing(
+            1_000,
+            Sizing::MemoryBudget(2_000),
+            None,
+            &Limits::default(),
+        )
+        .unwrap();
+
+        assert_eq!(configuration.get_total_bits(), 16_000);
+        assert_eq!(configuration.get_num_of_hash_funcs(), 11);
+        assert!(configuration.get_max_tolerance() < 0.0005);
+    }
+
+    #[test]
+    fn should_cap_hash_functions_when_budget_far_exceeds_need() {
+        for (max_size, bytes) in [(1_000, 1 << 20), (100, 4_000), (1, 1 << 30)] {
+            let configuration = Configuration::try_from_sizing(
+                max_size,
+                Sizing::MemoryBudget(bytes),
+                None,
+                &Limits::default(),
+            )
+            .unwrap();
+
+            assert_eq!(
+                configuration.get_num_of_hash_funcs(),
+                MAXIMUM_NUMBER_OF_HASH_FUNCTIONS
+            );
+            assert!(configuration.get_max_tolerance() > 0.0);
+        }
+    }
+
+    #[test]
+    fn should_return_err_when_sizing_is_invalid() {
+        let invalid_sizings = vec![
+            (Sizing::TotalBits(0), None),
+            (Sizing::TotalBits(8_000), Some(0)),
+            (
+                Sizing::TotalBits(8_000),
+                Some(MAXIMUM_NUMBER_OF_HASH_FUNCTIONS + 1),
+            ),
+            (Sizing::BitsPerEntry(0.0), None),
+            (Sizing::BitsPerEntry(-1.0), None),
+            (Sizing::MemoryBudget(0), None),
+            (Sizing::MemoryBudget(1), Some(100)),
+        ];
+
+        for (sizing, num_of_hash_funcs) in invalid_sizings {
+            assert!(Configuration::try_from_sizing(
+                1_000,
+                sizing,
+                num_of_hash_funcs,
+                &Limits::default()
+            )
+            .is_err());
+        }
+    }
+
+    #[test]
+    fn should_respect_given_limits() {
+        let limits = Limits::default()
+            .with_max_capacity(600_000_000)
+            .with_max_total_bits(1 << 40);
+
+        let configuration = Configuration::try_from(0.01, 600_000_000, &limits).unwrap();
+        assert!(configuration.get_total_bits() > 1 << 32);
+
+        assert!(Configuration::try_from(0.01, 600_000_000, &Limits::default()).is_err());
+        assert!(Configuration::try_from_sizing(
+            1_000,
+            Sizing::TotalBits(1 << 20),
+            None,
+            &Limits::default().with_max_total_bits(1 << 10)
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn should_restore_from_its_parts() {
+        let configuration = Configuration::try_from_sizing(
+            1_000,
+            Sizing::TotalBits(8_000),
+            Some(3),
+            &Limits::default(),
+        )
+        .unwrap();
+
+        let restored = configuration.try_clone_empty().unwrap();
+
+        assert_eq!(restored.get_total_bits(), 8_000);
+        assert_eq!(restored.get_num_of_hash_funcs(), 3);
+        assert_eq!(restored.get_max_size(), 1_000);
+        assert_eq!(
+            restored.get_max_tolerance(),
+            configuration.get_max_tolerance()
+        );
+    }
 }
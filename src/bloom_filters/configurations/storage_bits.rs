@@ -15,22 +15,41 @@ impl Deref for StorageBits {
 }
 
 impl StorageBits {
-    const MAXIMUM_BITS_ALLOWED_IN_STORAGE: usize = 512 * 1024 * 1024 * 1024;
+    // The limit is a u64 as it reaches past the usize of 32-bit platforms
+    pub(super) const DEFAULT_MAXIMUM_BITS_ALLOWED_IN_STORAGE: u64 = 512 * 1024 * 1024 * 1024;
 
     pub(super) fn try_from(
         capacity: &Capacity,
         tolerance: &Tolerance,
+        maximum: u64,
     ) -> Result<Self, &'static str> {
         let total_bits_needed = calculate_total_bits_based_on_max_size_and_tolerance(
             capacity.get_capacity(),
             tolerance.get_maximum_tolerance(),
         );
 
-        if total_bits_needed >= Self::MAXIMUM_BITS_ALLOWED_IN_STORAGE {
+        if total_bits_needed >= maximum {
             return Err("Too many bits needed for such tolerance and capacity. Overflow occurred");
         }
 
-        Ok(Self(total_bits_needed))
+        usize::try_from(total_bits_needed)
+            .map(Self)
+            .map_err(|_| "Too many bits needed for such tolerance and capacity on this platform")
+    }
+
+    pub(super) fn try_from_total_bits(
+        total_bits: usize,
+        maximum: u64,
+    ) -> Result<Self, &'static str> {
+        if total_bits as u64 >= maximum {
+            return Err("Too many bits requested for storage");
+        }
+
+        if total_bits == 0 {
+            return Err("Storage cannot have 0 bits");
+        }
+
+        Ok(Self(total_bits))
     }
 }
 
@@ -42,9 +61,35 @@ mod test {
 
     #[test]
     fn should_return_ok_when_bits_needed_not_maximum_allowed() {
-        let capacity = Capacity::try_from(1_000_000).unwrap();
+        let capacity = Capacity::try_from(1_000_000, 1_000_000).unwrap();
         let tolerance = Tolerance::try_from(0.01).unwrap();
-        let maybe_storage_bits = StorageBits::try_from(&capacity, &tolerance);
+        let maybe_storage_bits = StorageBits::try_from(
+            &capacity,
+            &tolerance,
+            StorageBits::DEFAULT_MAXIMUM_BITS_ALLOWED_IN_STORAGE,
+        );
         assert!(maybe_storage_bits.is_ok());
     }
+
+    #[test]
+    fn should_return_err_when_bits_needed_reach_given_maximum() {
+        let capacity = Capacity::try_from(1_000_000, 1_000_000).unwrap();
+        let tolerance = Tolerance::try_from(0.01).unwrap();
+        let maybe_storage_bits = StorageBits::try_from(&capacity, &tolerance, 1_000_000);
+        assert!(maybe_storage_bits.is_err());
+    }
+
+    #[test]
+    fn should_validate_explicit_total_bits() {
+        let maximum = StorageBits::DEFAULT_MAXIMUM_BITS_ALLOWED_IN_STORAGE;
+
+        assert!(StorageBits::try_from_total_bits(0, maximum).is_err());
+        assert!(StorageBits::try_from_total_bits(1_024, 1_024).is_err());
+
+        let storage_bits = StorageBits::try_from_total_bits(1_024, maximum).unwrap();
+        assert_eq!(*storage_bits, 1_024);
+
+        let storage_bits = StorageBits::try_from_total_bits(1 << 30, maximum).unwrap();
+        assert_eq!(*storage_bits, 1 << 30);
+    }
 }
@@ -3,23 +3,83 @@ use crate::bloom_filters::SupportedFloatingPointType;
 pub(super) fn calculate_total_bits_based_on_max_size_and_tolerance(
     max_size: usize,
     max_tolerance: SupportedFloatingPointType,
+) -> u64 {
+    let factor: SupportedFloatingPointType = 2.0;
+    (-(max_size as SupportedFloatingPointType) * max_tolerance.ln() / factor.ln() / factor.ln())
+        .ceil() as u64
+}
+
+// Past this many probes a lookup costs more than the false positives it saves
+pub(super) const MAXIMUM_NUMBER_OF_HASH_FUNCTIONS: usize = 64;
+
+// k = (m / n) * ln(2) minimizes the false positive rate of m bits holding n entries
+pub(super) fn calculate_optimal_number_of_hash_functions(
+    max_size: usize,
+    total_bits: usize,
 ) -> usize {
     let factor: SupportedFloatingPointType = 2.0;
-    (-((max_size as SupportedFloatingPointType) * max_tolerance.ln() / factor.ln() / factor.ln())
-        .ceil()) as usize
+    let bits_per_entry =
+        total_bits as SupportedFloatingPointType / max_size as SupportedFloatingPointType;
+    ((bits_per_entry * factor.ln()).round() as usize).clamp(1, MAXIMUM_NUMBER_OF_HASH_FUNCTIONS)
+}
+
+// p = (1 - e^(-k * n / m))^k
+pub(super) fn calculate_false_positive_rate(
+    max_size: usize,
+    total_bits: usize,
+    num_of_hash_funcs: usize,
+) -> SupportedFloatingPointType {
+    let num_of_hash_funcs_as_float = num_of_hash_funcs as SupportedFloatingPointType;
+    let fill_ratio = 1.0
+        - (-num_of_hash_funcs_as_float * max_size as SupportedFloatingPointType
+            / total_bits as SupportedFloatingPointType)
+            .exp();
+    fill_ratio.powi(num_of_hash_funcs as i32)
 }
 
 #[cfg(test)]
 mod test {
     use crate::bloom_filters::configurations::utility::calculate_total_bits_based_on_max_size_and_tolerance;
 
+    #[test]
+    fn should_provide_optimal_number_of_hash_functions() {
+        let test_cases = vec![
+            (1_000_000, 9_585_059, 7),
+            (1_000, 8_000, 6),
+            (1_000, 16_000, 11),
+            (1_000, 1_000, 1),
+            (1_000, 10, 1),
+        ];
+
+        for (max_size, total_bits, expected_number_of_hash_functions) in test_cases {
+            let number_of_hash_functions =
+                super::calculate_optimal_number_of_hash_functions(max_size, total_bits);
+            assert_eq!(number_of_hash_functions, expected_number_of_hash_functions);
+        }
+    }
+
+    #[test]
+    fn should_provide_expected_false_positive_rate() {
+        let test_cases = vec![
+            (1_000_000, 9_585_059, 7, 0.01),
+            (1_000, 8_000, 6, 0.0216),
+            (1_000, 16_000, 11, 0.000459),
+        ];
+
+        for (max_size, total_bits, num_of_hash_funcs, expected_rate) in test_cases {
+            let rate =
+                super::calculate_false_positive_rate(max_size, total_bits, num_of_hash_funcs);
+            assert!((rate - expected_rate).abs() / expected_rate < 0.01);
+        }
+    }
+
     #[test]
     fn should_return_correct_total_bits() {
         let test_cases = vec![
-            (1_000_000, 0.01, 9585058),
-            (1_000_000, 0.1, 4792529),
-            (1_000_000, 0.5, 1442695),
-            (1_000_000, 0.99, 20918),
+            (1_000_000, 0.01, 9585059),
+            (1_000_000, 0.1, 4792530),
+            (1_000_000, 0.5, 1442696),
+            (1_000_000, 0.99, 20919),
         ];
 
         for (max_size, max_tolerance, expected_total_bits) in test_cases {
@@ -28,4 +88,28 @@ mod test {
             assert_eq!(total_bits, expected_total_bits);
         }
     }
+
+    #[test]
+    fn should_match_closed_form_optimum_down_to_very_small_tolerances() {
+        let max_size = 1_000_000;
+
+        for exponent in 1..=12 {
+            let max_tolerance = 10f64.powi(-exponent);
+
+            let total_bits =
+                calculate_total_bits_based_on_max_size_and_tolerance(max_size, max_tolerance);
+            let optimal_total_bits = -(max_size as f64) * max_tolerance.ln() / 2f64.ln().powi(2);
+            assert_eq!(total_bits, optimal_total_bits.ceil() as u64);
+
+            let total_bits = total_bits as usize;
+
+            let num_of_hash_funcs =
+                super::calculate_optimal_number_of_hash_functions(max_size, total_bits);
+            assert_eq!(num_of_hash_funcs, (-max_tolerance.log2()).round() as usize);
+
+            let rate =
+                super::calculate_false_positive_rate(max_size, total_bits, num_of_hash_funcs);
+            assert!((rate - max_tolerance).abs() / max_tolerance < 0.01);
+        }
+    }
 }
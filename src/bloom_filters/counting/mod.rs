@@ -0,0 +1,136 @@
+use std::{hash::Hash, marker::PhantomData};
+
+use super::{
+    builder::Builder,
+    configurations::{Configurable, Configuration},
+    hashing::{strategy::Hashing, HashManager},
+    manage_hashing,
+    storage::CounterStorage,
+    BloomFilterError, Layout,
+};
+
+pub struct CountingBloomFilter<T: ?Sized = str> {
+    configuration: Configuration,
+    manager: HashManager,
+    storage: CounterStorage,
+    entry: PhantomData<fn(&T)>,
+}
+
+impl<T: Hash + ?Sized> CountingBloomFilter<T> {
+    pub fn builder() -> Builder<Self> {
+        Builder::default()
+    }
+
+    pub(super) fn try_from(
+        configuration: Configuration,
+        strategy: Option<Box<dyn Hashing>>,
+    ) -> Result<Self, BloomFilterError> {
+        let manager = manage_hashing(&configuration, strategy, Layout::Flat)?;
+
+        let storage = CounterStorage::try_from(configuration.get_total_bits())
+            .map_err(String::from)
+            .map_err(BloomFilterError::Storage)?;
+
+        Ok(Self {
+            configuration,
+            manager,
+            storage,
+            entry: PhantomData,
+        })
+    }
+
+    pub fn insert(&self, entry: &T) {
+        self.manager.hash(entry).for_each(|idx| {
+            self.storage.increment_at(idx);
+        });
+
+        self.configuration.increase_unique_entry_count();
+    }
+
+    pub fn contains(&self, entry: &T) -> bool {
+        self.manager
+            .hash(entry)
+            .all(|idx| self.storage.read_counter_at(idx) != 0)
+    }
+
+    pub fn remove(&self, entry: &T) -> bool {
+        let positions = self.manager.hash(entry);
+
+        if positions
+            .clone()
+            .any(|idx| self.storage.read_counter_at(idx) == 0)
+        {
+            return false;
+        }
+
+        positions.for_each(|idx| {
+            self.storage.decrement_at(idx);
+        });
+
+        self.configuration.decrease_unique_entry_count();
+
+        true
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::CountingBloomFilter;
+
+    #[test]
+    fn should_contain_inserted_entries() {
+        let filter: CountingBloomFilter = CountingBloomFilter::builder()
+            .with_max_size(1_000)
+            .build()
+            .unwrap();
+
+        filter.insert("hello");
+
+        assert!(filter.contains("hello"));
+        assert!(!filter.contains("world"));
+    }
+
+    #[test]
+    fn should_not_contain_removed_entries() {
+        let filter: CountingBloomFilter = CountingBloomFilter::builder()
+            .with_max_size(1_000)
+            .build()
+            .unwrap();
+
+        filter.insert("hello");
+        filter.insert("world");
+
+        assert!(filter.remove("hello"));
+        assert!(!filter.contains("hello"));
+        assert!(filter.contains("world"));
+    }
+
+    #[test]
+    fn should_keep_entries_inserted_more_than_once_until_removed_as_often() {
+        let filter: CountingBloomFilter = CountingBloomFilter::builder()
+            .with_max_size(1_000)
+            .build()
+            .unwrap();
+
+        filter.insert("hello");
+        filter.insert("hello");
+
+        assert!(filter.remove("hello"));
+        assert!(filter.contains("hello"));
+        assert!(filter.remove("hello"));
+        assert!(!filter.contains("hello"));
+    }
+
+    #[test]
+    fn should_not_remove_absent_entries() {
+        let filter: CountingBloomFilter = CountingBloomFilter::builder()
+            .with_max_size(1_000)
+            .build()
+            .unwrap();
+
+        filter.insert("hello");
+
+        assert!(!filter.remove("world"));
+        assert!(filter.contains("hello"));
+    }
+}
@@ -0,0 +1,154 @@
+use std::{hash::Hash, marker::PhantomData};
+
+use super::CuckooFilter;
+use crate::bloom_filters::{
+    configurations::{ConfigError, Limits},
+    hashing::strategy::Hashing,
+    BloomFilterError, SupportedFloatingPointType,
+};
+
+pub struct Builder<T: ?Sized = str> {
+    max_size: Option<usize>,
+    max_tolerance: Option<SupportedFloatingPointType>,
+    fingerprint_bits: Option<usize>,
+    bucket_size: Option<usize>,
+    max_relocations: Option<usize>,
+    strategy: Option<Box<dyn Hashing>>,
+    limits: Limits,
+    entry: PhantomData<fn(&T)>,
+}
+
+impl<T: ?Sized> Default for Builder<T> {
+    fn default() -> Self {
+        Self {
+            max_size: None,
+            max_tolerance: None,
+            fingerprint_bits: None,
+            bucket_size: None,
+            max_relocations: None,
+            strategy: None,
+            limits: Limits::default(),
+            entry: PhantomData,
+        }
+    }
+}
+
+impl<T: Hash + ?Sized> Builder<T> {
+    pub fn build(self) -> Result<CuckooFilter<T>, BloomFilterError> {
+        let bucket_size = self.bucket_size.unwrap_or(4);
+        let max_tolerance = self.max_tolerance.unwrap_or(0.01);
+
+        if max_tolerance <= 0.0 || max_tolerance >= 1.0 {
+            return Err(BloomFilterError::Configuration(ConfigError::Validation(
+                String::from("Tolerance must be within 0.0 and 1.0"),
+            )));
+        }
+
+        // A lookup compares 2 * bucket_size fingerprints, each matching by chance with 1 / 2^f
+        let fingerprint_bits = self.fingerprint_bits.unwrap_or(
+            (2.0 * bucket_size as SupportedFloatingPointType / max_tolerance)
+                .log2()
+                .ceil() as usize,
+        );
+
+        CuckooFilter::try_from(
+            self.max_size.unwrap_or(1_000_000),
+            fingerprint_bits,
+            bucket_size,
+            self.max_relocations.unwrap_or(500),
+            self.strategy,
+            &self.limits,
+        )
+    }
+
+    pub fn with_max_size(self, max_size: usize) -> Self {
+        Self {
+            max_size: Some(max_size),
+            max_tolerance: self.max_tolerance,
+            fingerprint_bits: self.fingerprint_bits,
+            bucket_size: self.bucket_size,
+            max_relocations: self.max_relocations,
+            strategy: self.strategy,
+            limits: self.limits,
+            entry: PhantomData,
+        }
+    }
+
+    pub fn with_max_tolerance(self, max_tolerance: SupportedFloatingPointType) -> Self {
+        Self {
+            max_size: self.max_size,
+            max_tolerance: Some(max_tolerance),
+            fingerprint_bits: self.fingerprint_bits,
+            bucket_size: self.bucket_size,
+            max_relocations: self.max_relocations,
+            strategy: self.strategy,
+            limits: self.limits,
+            entry: PhantomData,
+        }
+    }
+
+    pub fn with_fingerprint_bits(self, fingerprint_bits: usize) -> Self {
+        Self {
+            max_size: self.max_size,
+            max_tolerance: self.max_tolerance,
+            fingerprint_bits: Some(fingerprint_bits),
+            bucket_size: self.bucket_size,
+            max_relocations: self.max_relocations,
+            strategy: self.strategy,
+            limits: self.limits,
+            entry: PhantomData,
+        }
+    }
+
+    pub fn with_bucket_size(self, bucket_size: usize) -> Self {
+        Self {
+            max_size: self.max_size,
+            max_tolerance: self.max_tolerance,
+            fingerprint_bits: self.fingerprint_bits,
+            bucket_size: Some(bucket_size),
+            max_relocations: self.max_relocations,
+            strategy: self.strategy,
+            limits: self.limits,
+            entry: PhantomData,
+        }
+    }
+
+    pub fn with_max_relocations(self, max_relocations: usize) -> Self {
+        Self {
+            max_size: self.max_size,
+            max_tolerance: self.max_tolerance,
+            fingerprint_bits: self.fingerprint_bits,
+            bucket_size: self.bucket_size,
+            max_relocations: Some(max_relocations),
+            strategy: self.strategy,
+            limits: self.limits,
+            entry: PhantomData,
+        }
+    }
+
+    pub fn with_strategy(self, strategy: Box<dyn Hashing>) -> Self {
+        Self {
+            max_size: self.max_size,
+            max_tolerance: self.max_tolerance,
+            fingerprint_bits: self.fingerprint_bits,
+            bucket_size: self.bucket_size,
+            max_relocations: self.max_relocations,
+            strategy: Some(strategy),
+            limits: self.limits,
+            entry: PhantomData,
+        }
+    }
+
+    pub fn with_total_bits_limit(self, max_total_bits: u64) -> Self {
+        Self {
+            max_size: self.max_size,
+            max_tolerance: self.max_tolerance,
+            fingerprint_bits: self.fingerprint_bits,
+            bucket_size: self.bucket_size,
+            max_relocations: self.max_relocations,
+            strategy: self.strategy,
+            limits: self.limits.with_max_total_bits(max_total_bits),
+            entry: PhantomData,
+        }
+    }
+}
@@ -0,0 +1,396 @@
+use std::{
+    hash::Hash,
+    marker::PhantomData,
+    sync::{PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard},
+};
+
+use builder::Builder;
+use table::FingerprintTable;
+
+use super::{
+    configurations::{ConfigError, Limits},
+    hashing::{
+        strategy::{remix, Hashing},
+        HashManager,
+    },
+    BloomFilterError, SupportedFloatingPointType,
+};
+
+mod builder;
+mod table;
+
+struct Buckets {
+    table: FingerprintTable,
+    len: usize,
+    random_state: u64,
+}
+
+// CuckooFilter follows Fan et al.: every entry keeps a fingerprint in one of two buckets, the second
+// one being the first XOR the hash of the fingerprint, so either bucket can be found from the other
+pub struct CuckooFilter<T: ?Sized = str> {
+    manager: HashManager,
+    buckets: RwLock<Buckets>,
+    num_of_buckets: usize,
+    fingerprint_bits: usize,
+    max_relocations: usize,
+    entry: PhantomData<fn(&T)>,
+}
+
+impl<T: Hash + ?Sized> CuckooFilter<T> {
+    const MAXIMUM_FINGERPRINT_BITS: usize = u32::BITS as usize;
+    const MAXIMUM_BUCKET_SIZE: usize = 8;
+    const MAXIMUM_LOAD_FACTOR: SupportedFloatingPointType = 0.95;
+
+    pub fn builder() -> Builder<T> {
+        Builder::default()
+    }
+
+    fn try_from(
+        max_size: usize,
+        fingerprint_bits: usize,
+        bucket_size: usize,
+        max_relocations: usize,
+        strategy: Option<Box<dyn Hashing>>,
+        limits: &Limits,
+    ) -> Result<Self, BloomFilterError> {
+        let validation_error = if max_size == 0 {
+            Some(String::from("Storage cannot have capacity of 0"))
+        } else if fingerprint_bits == 0 || fingerprint_bits > Self::MAXIMUM_FINGERPRINT_BITS {
+            Some(format!(
+                "Fingerprint bits must be within 1 and {}",
+                Self::MAXIMUM_FINGERPRINT_BITS
+            ))
+        } else if bucket_size == 0 || bucket_size > Self::MAXIMUM_BUCKET_SIZE {
+            Some(format!(
+                "Bucket size must be within 1 and {}",
+                Self::MAXIMUM_BUCKET_SIZE
+            ))
+        } else if max_relocations == 0 {
+            Some(String::from("Maximum relocations must be positive"))
+        } else {
+            None
+        };
+
+        if let Some(message) = validation_error {
+            return Err(BloomFilterError::Configuration(ConfigError::Validation(
+                message,
+            )));
+        }
+
+        // Alternate buckets are found by XOR, which only stays in range for a power of two buckets
+        let num_of_buckets = ((max_size as SupportedFloatingPointType
+            / (bucket_size as SupportedFloatingPointType * Self::MAXIMUM_LOAD_FACTOR))
+            .ceil() as usize)
+            .checked_next_power_of_two()
+            .ok_or_else(|| {
+                BloomFilterError::Configuration(ConfigError::Dependency(String::from(
+                    "Too many buckets needed for such capacity",
+                )))
+            })?
+            .max(2);
+
+        let table = FingerprintTable::try_from(
+            num_of_buckets,
+            bucket_size,
+            fingerprint_bits,
+            limits.get_max_total_bits(),
+        )
+        .map_err(String::from)
+        .map_err(ConfigError::Dependency)
+        .map_err(BloomFilterError::Configuration)?;
+
+        let mut manager_builder = HashManager::builder()
+            .with_num_of_hash_funcs(2)
+            .with_total_bits(num_of_buckets);
+
+        if let Some(strategy) = strategy {
+            manager_builder = manager_builder.use_strategy(strategy);
+        }
+
+        let manager = manager_builder
+            .build()
+            .map_err(String::from)
+            .map_err(BloomFilterError::Hashing)?;
+
+        let buckets = RwLock::new(Buckets {
+            table,
+            len: 0,
+            random_state: remix(manager.get_seed()),
+        });
+
+        Ok(Self {
+            manager,
+            buckets,
+            num_of_buckets,
+            fingerprint_bits,
+            max_relocations,
+            entry: PhantomData,
+        })
+    }
+
+    pub fn insert(&self, entry: &T) -> Result<(), BloomFilterError> {
+        let (bucket, fingerprint) = self.locate(entry);
+        let alternate_bucket = self.find_alternate_bucket(bucket, fingerprint);
+
+        let mut buckets = self.write_buckets();
+
+        for candidate in [bucket, alternate_bucket] {
+            if let Some(slot) = buckets.table.find_in_bucket(candidate, 0) {
+                buckets.table.write(slot, fingerprint);
+                buckets.len += 1;
+                return Ok(());
+            }
+        }
+
+        let mut relocated_slots = Vec::with_capacity(self.max_relocations);
+        let (mut bucket, mut fingerprint) = match buckets.next_random() % 2 {
+            0 => (bucket, fingerprint),
+            _ => (alternate_bucket, fingerprint),
+        };
+
+        for _ in 0..self.max_relocations {
+            let bucket_size = buckets.table.get_bucket_size();
+            let slot = bucket * bucket_size + (buckets.next_random() as usize % bucket_size);
+
+            let evicted = buckets.table.read(slot);
+            buckets.table.write(slot, fingerprint);
+            relocated_slots.push((slot, evicted));
+
+            fingerprint = evicted;
+            bucket = self.find_alternate_bucket(bucket, fingerprint);
+
+            if let Some(slot) = buckets.table.find_in_bucket(bucket, 0) {
+                buckets.table.write(slot, fingerprint);
+                buckets.len += 1;
+                return Ok(());
+            }
+        }
+
+        // Undoing every relocation keeps all entries inserted so far reachable
+        relocated_slots
+            .into_iter()
+            .rev()
+            .for_each(|(slot, evicted)| buckets.table.write(slot, evicted));
+
+        Err(BloomFilterError::Full(format!(
+            "No free slot found after {} relocations",
+            self.max_relocations
+        )))
+    }
+
+    pub fn contains(&self, entry: &T) -> bool {
+        let (bucket, fingerprint) = self.locate(entry);
+        let alternate_bucket = self.find_alternate_bucket(bucket, fingerprint);
+
+        let buckets = self.read_buckets();
+
+        [bucket, alternate_bucket].into_iter().any(|candidate| {
+            buckets
+                .table
+                .find_in_bucket(candidate, fingerprint)
+                .is_some()
+        })
+    }
+
+    pub fn remove(&self, entry: &T) -> bool {
+        let (bucket, fingerprint) = self.locate(entry);
+        let alternate_bucket = self.find_alternate_bucket(bucket, fingerprint);
+
+        let mut buckets = self.write_buckets();
+
+        let maybe_slot = [bucket, alternate_bucket]
+            .into_iter()
+            .find_map(|candidate| buckets.table.find_in_bucket(candidate, fingerprint));
+
+        match maybe_slot {
+            Some(slot) => {
+                buckets.table.write(slot, 0);
+                buckets.len -= 1;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.read_buckets().len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn get_size_in_bytes(&self) -> usize {
+        self.read_buckets().table.get_size_in_bytes()
+    }
+
+    // A lookup matches by chance whenever one of the 2 * b fingerprints it compares equals its own
+    pub fn expected_false_positive_rate(&self) -> SupportedFloatingPointType {
+        let buckets = self.read_buckets();
+        let num_of_fingerprints =
+            ((1u64 << buckets.table.get_fingerprint_bits()) - 1) as SupportedFloatingPointType;
+
+        1.0 - (1.0 - 1.0 / num_of_fingerprints).powi(2 * buckets.table.get_bucket_size() as i32)
+    }
+
+    fn locate(&self, entry: &T) -> (usize, u32) {
+        let (index_hash, fingerprint_hash) = self.manager.hash_base(entry);
+        let fingerprint = (fingerprint_hash & ((1u64 << self.fingerprint_bits) - 1)) as u32;
+
+        (
+            (index_hash & (self.num_of_buckets as u64 - 1)) as usize,
+            fingerprint.max(1),
+        )
+    }
+
+    fn find_alternate_bucket(&self, bucket: usize, fingerprint: u32) -> usize {
+        bucket ^ (remix(fingerprint as u64) & (self.num_of_buckets as u64 - 1)) as usize
+    }
+
+    fn read_buckets(&self) -> RwLockReadGuard<'_, Buckets> {
+        self.buckets.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn write_buckets(&self) -> RwLockWriteGuard<'_, Buckets> {
+        self.buckets.write().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Buckets {
+    // xorshift64*, only ever advanced under the write lock
+    fn next_random(&mut self) -> u64 {
+        self.random_state ^= self.random_state >> 12;
+        self.random_state ^= self.random_state << 25;
+        self.random_state ^= self.random_state >> 27;
+        self.random_state.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::bloom_filters::BloomFilterError;
+
+    use super::{ConfigError, CuckooFilter};
+
+    #[test]
+    fn should_contain_inserted_entries() {
+        let filter: CuckooFilter = CuckooFilter::builder()
+            .with_max_size(1_000)
+            .build()
+            .unwrap();
+
+        filter.insert("hello").unwrap();
+
+        assert!(filter.contains("hello"));
+        assert!(!filter.contains("world"));
+        assert_eq!(filter.len(), 1);
+    }
+
+    #[test]
+    fn should_not_contain_removed_entries() {
+        let filter: CuckooFilter = CuckooFilter::builder()
+            .with_max_size(1_000)
+            .build()
+            .unwrap();
+
+        filter.insert("hello").unwrap();
+        filter.insert("world").unwrap();
+
+        assert!(filter.remove("hello"));
+        assert!(!filter.contains("hello"));
+        assert!(filter.contains("world"));
+        assert!(!filter.remove("hello"));
+        assert_eq!(filter.len(), 1);
+    }
+
+    #[test]
+    fn should_keep_entries_reachable_when_filled_to_capacity() {
+        let filter: CuckooFilter<u64> = CuckooFilter::builder()
+            .with_max_size(10_000)
+            .with_fingerprint_bits(12)
+            .with_bucket_size(4)
+            .build()
+            .unwrap();
+
+        (0..10_000).for_each(|entry| filter.insert(&entry).unwrap());
+
+        assert!((0..10_000).all(|entry| filter.contains(&entry)));
+
+        let false_positives = (10_000..110_000)
+            .filter(|entry| filter.contains(entry))
+            .count();
+
+        assert!(false_positives as f64 / 100_000.0 <= filter.expected_false_positive_rate());
+    }
+
+    #[test]
+    fn should_return_err_and_keep_entries_when_full() {
+        let filter: CuckooFilter<u64> = CuckooFilter::builder()
+            .with_max_size(64)
+            .with_fingerprint_bits(16)
+            .with_bucket_size(2)
+            .with_max_relocations(50)
+            .build()
+            .unwrap();
+
+        let (inserted, rejected): (Vec<u64>, Vec<u64>) =
+            (0..1_000).partition(|entry| match filter.insert(entry) {
+                Ok(()) => true,
+                Err(err) => {
+                    assert!(matches!(err, BloomFilterError::Full(_)));
+                    false
+                }
+            });
+
+        assert!(!rejected.is_empty());
+        assert!(inserted.iter().all(|entry| filter.contains(entry)));
+        assert_eq!(filter.len(), inserted.len());
+    }
+
+    #[test]
+    fn should_pick_fingerprint_bits_from_tolerance() {
+        let filter: CuckooFilter = CuckooFilter::builder()
+            .with_max_size(1_000)
+            .with_max_tolerance(0.001)
+            .build()
+            .unwrap();
+
+        assert!(filter.expected_false_positive_rate() <= 0.001);
+        assert!(filter.expected_false_positive_rate() > 0.0005);
+    }
+
+    #[test]
+    fn should_return_err_when_configuration_is_invalid() {
+        let invalid_builders = vec![
+            CuckooFilter::<str>::builder().with_max_size(0),
+            CuckooFilter::<str>::builder().with_fingerprint_bits(0),
+            CuckooFilter::<str>::builder().with_fingerprint_bits(33),
+            CuckooFilter::<str>::builder().with_bucket_size(0),
+            CuckooFilter::<str>::builder().with_bucket_size(9),
+            CuckooFilter::<str>::builder().with_max_relocations(0),
+            CuckooFilter::<str>::builder().with_max_tolerance(1e-12),
+        ];
+
+        for builder in invalid_builders {
+            assert!(builder.build().is_err());
+        }
+    }
+
+    #[test]
+    fn should_return_err_without_allocating_when_table_is_too_large() {
+        let oversized_builders = vec![
+            CuckooFilter::<str>::builder()
+                .with_max_size(usize::MAX)
+                .with_bucket_size(1),
+            CuckooFilter::<str>::builder().with_max_size(1 << 40),
+            CuckooFilter::<str>::builder().with_total_bits_limit(1_000),
+        ];
+
+        for builder in oversized_builders {
+            assert!(matches!(
+                builder.build(),
+                Err(BloomFilterError::Configuration(ConfigError::Dependency(_)))
+            ));
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use std::ops::Range;
+
+// FingerprintTable packs fingerprints of up to 32 bits back to back, a fingerprint of 0 marks an empty slot
+pub(super) struct FingerprintTable {
+    words: Vec<u64>,
+    fingerprint_bits: usize,
+    bucket_size: usize,
+}
+
+impl FingerprintTable {
+    const WORD_BITS: usize = u64::BITS as usize;
+
+    pub(super) fn try_from(
+        num_of_buckets: usize,
+        bucket_size: usize,
+        fingerprint_bits: usize,
+        max_total_bits: u64,
+    ) -> Result<Self, &'static str> {
+        let total_bits = num_of_buckets
+            .checked_mul(bucket_size)
+            .and_then(|num_of_slots| num_of_slots.checked_mul(fingerprint_bits))
+            .filter(|&total_bits| (total_bits as u64) < max_total_bits)
+            .ok_or("Too many bits requested for storage")?;
+
+        Ok(Self {
+            words: vec![0; total_bits.div_ceil(Self::WORD_BITS)],
+            fingerprint_bits,
+            bucket_size,
+        })
+    }
+
+    pub(super) fn read(&self, slot: usize) -> u32 {
+        let (word, shift) = self.read_slot_coordinates(slot);
+
+        let mut value = self.words[word] >> shift;
+
+        if shift + self.fingerprint_bits > Self::WORD_BITS {
+            value |= self.words[word + 1] << (Self::WORD_BITS - shift);
+        }
+
+        (value & self.mask()) as u32
+    }
+
+    pub(super) fn write(&mut self, slot: usize, fingerprint: u32) {
+        let (word, shift) = self.read_slot_coordinates(slot);
+        let (mask, value) = (self.mask(), fingerprint as u64 & self.mask());
+
+        self.words[word] = (self.words[word] & !(mask << shift)) | (value << shift);
+
+        // A fingerprint straddling two words keeps its upper bits at the start of the next one
+        if shift + self.fingerprint_bits > Self::WORD_BITS {
+            let written_bits = Self::WORD_BITS - shift;
+            self.words[word + 1] =
+                (self.words[word + 1] & !(mask >> written_bits)) | (value >> written_bits);
+        }
+    }
+
+    pub(super) fn find_in_bucket(&self, bucket: usize, fingerprint: u32) -> Option<usize> {
+        self.read_bucket_slots(bucket)
+            .find(|&slot| self.read(slot) == fingerprint)
+    }
+
+    pub(super) fn get_bucket_size(&self) -> usize {
+        self.bucket_size
+    }
+
+    pub(super) fn get_fingerprint_bits(&self) -> usize {
+        self.fingerprint_bits
+    }
+
+    pub(super) fn get_size_in_bytes(&self) -> usize {
+        self.words.len() * std::mem::size_of::<u64>()
+    }
+
+    fn read_bucket_slots(&self, bucket: usize) -> Range<usize> {
+        bucket * self.bucket_size..(bucket + 1) * self.bucket_size
+    }
+
+    fn read_slot_coordinates(&self, slot: usize) -> (usize, usize) {
+        let offset = slot * self.fingerprint_bits;
+        (offset / Self::WORD_BITS, offset % Self::WORD_BITS)
+    }
+
+    fn mask(&self) -> u64 {
+        (1u64 << self.fingerprint_bits) - 1
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::FingerprintTable;
+
+    #[test]
+    fn should_read_back_fingerprints_of_any_width() {
+        for fingerprint_bits in [1, 7, 8, 12, 16, 31, 32] {
+            let mut table = FingerprintTable::try_from(16, 4, fingerprint_bits, u64::MAX).unwrap();
+            let mask = ((1u64 << fingerprint_bits) - 1) as u32;
+
+            (0..64).for_each(|slot| {
+                table.write(slot, (slot as u32).wrapping_mul(2_654_435_761) & mask)
+            });
+
+            assert!((0..64)
+                .all(|slot| table.read(slot) == (slot as u32).wrapping_mul(2_654_435_761) & mask));
+        }
+    }
+
+    #[test]
+    fn should_find_fingerprints_and_empty_slots_within_bucket() {
+        let mut table = FingerprintTable::try_from(2, 4, 12, u64::MAX).unwrap();
+
+        table.write(5, 0xabc);
+
+        assert_eq!(table.find_in_bucket(1, 0xabc), Some(5));
+        assert_eq!(table.find_in_bucket(0, 0xabc), None);
+        assert_eq!(table.find_in_bucket(1, 0), Some(4));
+    }
+}
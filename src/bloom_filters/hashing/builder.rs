@@ -1,49 +1,75 @@
-use crate::bloom_filters::SupportedFloatingPointType;
-
 use super::{
+    layout::Layout,
     seed::Seed,
     strategy::{DefaultHashingStrategy, Hashing},
-    utility::calculate_number_of_hash_functions_based_on_tolerance,
     HashManager,
 };
 
 #[derive(Default)]
 pub(crate) struct Builder {
-    max_tolerance: Option<SupportedFloatingPointType>,
+    num_of_hash_funcs: Option<usize>,
     total_bits: Option<usize>,
+    layout: Option<Layout>,
+    fold_factor: Option<usize>,
     strategy: Option<Box<dyn Hashing>>,
 }
 
 impl Builder {
-    pub(crate) fn with_tolerance(self, size: SupportedFloatingPointType) -> Self {
+    pub(crate) fn with_num_of_hash_funcs(self, num_of_hash_funcs: usize) -> Self {
         Self {
-            max_tolerance: Some(size),
+            num_of_hash_funcs: Some(num_of_hash_funcs),
             total_bits: self.total_bits,
+            layout: self.layout,
+            fold_factor: self.fold_factor,
             strategy: self.strategy,
         }
     }
 
     pub(crate) fn with_total_bits(self, total_bits: usize) -> Self {
         Self {
-            max_tolerance: self.max_tolerance,
+            num_of_hash_funcs: self.num_of_hash_funcs,
             total_bits: Some(total_bits),
+            layout: self.layout,
+            fold_factor: self.fold_factor,
+            strategy: self.strategy,
+        }
+    }
+
+    pub(crate) fn with_layout(self, layout: Layout) -> Self {
+        Self {
+            num_of_hash_funcs: self.num_of_hash_funcs,
+            total_bits: self.total_bits,
+            layout: Some(layout),
+            fold_factor: self.fold_factor,
+            strategy: self.strategy,
+        }
+    }
+
+    pub(crate) fn with_fold_factor(self, fold_factor: usize) -> Self {
+        Self {
+            num_of_hash_funcs: self.num_of_hash_funcs,
+            total_bits: self.total_bits,
+            layout: self.layout,
+            fold_factor: Some(fold_factor),
             strategy: self.strategy,
         }
     }
 
     pub(crate) fn use_strategy(self, hash_strategy: Box<dyn Hashing>) -> Self {
         Self {
-            max_tolerance: self.max_tolerance,
+            num_of_hash_funcs: self.num_of_hash_funcs,
             total_bits: self.total_bits,
+            layout: self.layout,
+            fold_factor: self.fold_factor,
             strategy: Some(hash_strategy),
         }
     }
 
     pub(crate) fn build(self) -> Result<HashManager, &'static str> {
-        let max_tolerance = match self.max_tolerance {
-            Some(value) if value > 0.0 || value < 1.0 => value,
-            Some(_) => return Err("Invalid maximum tolerance value"),
-            None => return Err("Maximum tolerance is not provided"),
+        let num_of_hash_funcs = match self.num_of_hash_funcs {
+            Some(value) if value > 0 => value,
+            Some(_) => return Err("Number of hash functions cannot be zero"),
+            None => return Err("Number of hash functions is not provided"),
         };
 
         let strategy = match self.strategy {
@@ -57,13 +83,24 @@ impl Builder {
             None => return Err("Total bits is not provided"),
         };
 
-        let num_of_hash_funcs =
-            calculate_number_of_hash_functions_based_on_tolerance(max_tolerance);
+        let layout = self.layout.unwrap_or_default();
+
+        if layout == Layout::Partitioned && total_bits < num_of_hash_funcs {
+            return Err("Total bits cannot be split into one slice per hash function");
+        }
+
+        let fold_factor = match self.fold_factor {
+            Some(value) if value.is_power_of_two() && total_bits.is_multiple_of(value) => value,
+            Some(_) => return Err("Fold factor must be a power of two dividing total bits"),
+            None => 1,
+        };
 
         Ok(HashManager {
             num_of_hash_funcs,
             strategy,
             total_bits,
+            layout,
+            fold_factor,
         })
     }
 }
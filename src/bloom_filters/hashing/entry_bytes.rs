@@ -0,0 +1,139 @@
+use std::{
+    hash::{Hash, Hasher},
+    ops::Deref,
+};
+
+// EntryBytes records what an entry feeds into a Hasher, so strategies hash the same bytes for any key type.
+// Integers are widened to fixed-width little-endian bytes to stay independent of the platform.
+// Bytes stay on the stack and only spill to the heap for entries longer than the inline buffer.
+pub(super) struct EntryBytes {
+    inline: [u8; Self::INLINE_CAPACITY],
+    len: usize,
+    spilled: Vec<u8>,
+}
+
+impl Deref for EntryBytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        match self.spilled.is_empty() {
+            true => &self.inline[..self.len],
+            false => &self.spilled,
+        }
+    }
+}
+
+impl EntryBytes {
+    const INLINE_CAPACITY: usize = 128;
+
+    pub(super) fn from<T: Hash + ?Sized>(entry: &T) -> Self {
+        let mut bytes = Self {
+            inline: [0; Self::INLINE_CAPACITY],
+            len: 0,
+            spilled: Vec::new(),
+        };
+        entry.hash(&mut bytes);
+        bytes
+    }
+}
+
+impl Hasher for EntryBytes {
+    fn finish(&self) -> u64 {
+        unreachable!("EntryBytes only collects bytes, strategies do the hashing")
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        if !self.spilled.is_empty() {
+            self.spilled.extend_from_slice(bytes);
+        } else if self.len + bytes.len() <= Self::INLINE_CAPACITY {
+            self.inline[self.len..self.len + bytes.len()].copy_from_slice(bytes);
+            self.len += bytes.len();
+        } else {
+            self.spilled.reserve(self.len + bytes.len());
+            self.spilled.extend_from_slice(&self.inline[..self.len]);
+            self.spilled.extend_from_slice(bytes);
+        }
+    }
+
+    fn write_u16(&mut self, value: u16) {
+        self.write(&value.to_le_bytes());
+    }
+
+    fn write_u32(&mut self, value: u32) {
+        self.write(&value.to_le_bytes());
+    }
+
+    fn write_u64(&mut self, value: u64) {
+        self.write(&value.to_le_bytes());
+    }
+
+    fn write_u128(&mut self, value: u128) {
+        self.write(&value.to_le_bytes());
+    }
+
+    fn write_usize(&mut self, value: usize) {
+        self.write_u64(value as u64);
+    }
+
+    fn write_i16(&mut self, value: i16) {
+        self.write_u16(value as u16);
+    }
+
+    fn write_i32(&mut self, value: i32) {
+        self.write_u32(value as u32);
+    }
+
+    fn write_i64(&mut self, value: i64) {
+        self.write_u64(value as u64);
+    }
+
+    fn write_i128(&mut self, value: i128) {
+        self.write_u128(value as u128);
+    }
+
+    fn write_isize(&mut self, value: isize) {
+        self.write_u64(value as i64 as u64);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::EntryBytes;
+
+    #[test]
+    fn should_record_bytes_of_strings() {
+        let bytes = EntryBytes::from("abc");
+        assert_eq!(&*bytes, &[b'a', b'b', b'c', 0xff]);
+    }
+
+    #[test]
+    fn should_record_integers_as_fixed_width_little_endian_bytes() {
+        assert_eq!(&*EntryBytes::from(&1u16), &[1, 0]);
+        assert_eq!(&*EntryBytes::from(&1usize), &[1, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(&*EntryBytes::from(&-1isize), &[0xff; 8]);
+        assert_eq!(&*EntryBytes::from(&1usize), &*EntryBytes::from(&1u64));
+    }
+
+    #[test]
+    fn should_record_entries_longer_than_the_inline_buffer() {
+        let entry = "x".repeat(EntryBytes::INLINE_CAPACITY + 1);
+
+        let mut expected = entry.clone().into_bytes();
+        expected.push(0xff);
+
+        assert_eq!(&*EntryBytes::from(entry.as_str()), expected.as_slice());
+        assert_eq!(
+            EntryBytes::from(&(7u64, entry.as_str()))[..8],
+            7u64.to_le_bytes()
+        );
+    }
+
+    #[test]
+    fn should_record_the_same_bytes_for_equal_entries() {
+        let entry = (42u32, "hello", [1u8, 2, 3]);
+        assert_eq!(
+            &*EntryBytes::from(&entry),
+            &*EntryBytes::from(&entry.clone())
+        );
+    }
+}
@@ -0,0 +1,49 @@
+// Layout decides where the probes of an entry may land in the storage:
+// - Flat spreads them over every bit
+// - Partitioned splits the bits into one slice per hash function and probes each slice once
+// - Blocked keeps every probe inside one block of 512 bits, the size of a cache line
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Layout {
+    #[default]
+    Flat,
+    Partitioned,
+    Blocked,
+}
+
+impl Layout {
+    pub(crate) const BLOCK_BITS: usize = 512;
+
+    pub(crate) fn get_identifier(&self) -> u8 {
+        match self {
+            Self::Flat => 0,
+            Self::Partitioned => 1,
+            Self::Blocked => 2,
+        }
+    }
+
+    pub(crate) fn from_identifier(identifier: u8) -> Option<Self> {
+        match identifier {
+            0 => Some(Self::Flat),
+            1 => Some(Self::Partitioned),
+            2 => Some(Self::Blocked),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Layout;
+
+    #[test]
+    fn should_restore_layout_from_its_identifier() {
+        for layout in [Layout::Flat, Layout::Partitioned, Layout::Blocked] {
+            assert_eq!(
+                Layout::from_identifier(layout.get_identifier()),
+                Some(layout)
+            );
+        }
+
+        assert_eq!(Layout::from_identifier(u8::MAX), None);
+    }
+}
@@ -1,25 +1,244 @@
+use std::{fmt, hash::Hash};
+
 use builder::Builder;
+use entry_bytes::EntryBytes;
+pub use layout::Layout;
+pub(crate) use positions::Positions;
 use strategy::Hashing;
 
 mod builder;
+mod entry_bytes;
+mod layout;
+mod positions;
 mod seed;
 pub mod strategy;
-mod utility;
 
 pub(crate) struct HashManager {
     num_of_hash_funcs: usize,
     total_bits: usize,
+    layout: Layout,
+    fold_factor: usize,
     strategy: Box<dyn Hashing>,
 }
 
 impl HashManager {
+    const PROBE: &'static [u8] = b"restorable";
+
     pub(crate) fn builder() -> Builder {
         Builder::default()
     }
 
-    pub(crate) fn hash(&self, entry: &dyn ToString) -> Vec<usize> {
-        (0..self.num_of_hash_funcs)
-            .map(|idx| self.strategy.hash(idx, self.total_bits, entry))
-            .collect()
+    pub(crate) fn get_num_of_hash_funcs(&self) -> usize {
+        self.num_of_hash_funcs
+    }
+
+    pub(crate) fn get_layout(&self) -> Layout {
+        self.layout
+    }
+
+    pub(crate) fn get_fold_factor(&self) -> usize {
+        self.fold_factor
+    }
+
+    pub(crate) fn get_seed(&self) -> u64 {
+        self.strategy.get_seed()
+    }
+
+    pub(crate) fn get_strategy_identifier(&self) -> u8 {
+        self.strategy.get_identifier()
+    }
+
+    pub(crate) fn get_strategy_name(&self) -> &'static str {
+        strategy::name_of(self.get_strategy_identifier())
+    }
+
+    pub(crate) fn hash<T: Hash + ?Sized>(&self, entry: &T) -> Positions {
+        let positions = Positions::from(
+            self.hash_base(entry),
+            self.num_of_hash_funcs,
+            self.total_bits,
+            self.layout,
+        );
+
+        // Only a folded filter pays for wrapping its positions
+        match self.fold_factor {
+            1 => positions,
+            fold_factor => positions.fold_into(self.total_bits / fold_factor),
+        }
+    }
+
+    pub(crate) fn hash_base<T: Hash + ?Sized>(&self, entry: &T) -> (u64, u64) {
+        self.strategy.hash(&EntryBytes::from(entry))
+    }
+
+    // Only a strategy hashing exactly like the built-in its identifier names can be rebuilt after loading
+    pub(crate) fn try_restore_strategy(&self) -> Result<Box<dyn Hashing>, &'static str> {
+        strategy::from_identifier(self.get_strategy_identifier(), self.get_seed())
+            .filter(|restored| restored.hash(Self::PROBE) == self.strategy.hash(Self::PROBE))
+            .ok_or("Hashing strategy cannot be restored from its identifier")
+    }
+
+    pub(crate) fn try_clone(&self) -> Result<Self, &'static str> {
+        self.try_fold(1)
+    }
+
+    // A folded manager hashes over the same range as before and wraps every position into the folded bits
+    pub(crate) fn try_fold(&self, factor: usize) -> Result<Self, &'static str> {
+        let strategy = self.try_restore_strategy()?;
+
+        Self::builder()
+            .with_num_of_hash_funcs(self.num_of_hash_funcs)
+            .with_total_bits(self.total_bits)
+            .with_layout(self.layout)
+            .with_fold_factor(self.fold_factor * factor)
+            .use_strategy(strategy)
+            .build()
+    }
+}
+
+impl fmt::Debug for HashManager {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HashManager")
+            .field("num_of_hash_funcs", &self.num_of_hash_funcs)
+            .field("total_bits", &self.total_bits)
+            .field("layout", &self.layout)
+            .field("fold_factor", &self.fold_factor)
+            .field("strategy", &self.get_strategy_name())
+            .field("seed", &self.get_seed())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        strategy::{
+            DefaultHashingStrategy, Hashing, Murmur3HashingStrategy, SeaHashingStrategy,
+            SipHash13HashingStrategy, Xxh3HashingStrategy,
+        },
+        HashManager, Layout,
+    };
+
+    // Golden values pin the bits a persisted filter probes, any change here breaks every saved filter
+    #[test]
+    fn should_probe_pinned_positions_on_every_platform() {
+        let expected = [
+            (
+                Layout::Flat,
+                "hello",
+                [497294, 3026775, 5556257, 8085741, 1030169, 3559660, 6089156],
+            ),
+            (
+                Layout::Flat,
+                "world",
+                [
+                    8443384, 3632740, 8407156, 3596515, 8370936, 3560302, 8334732,
+                ],
+            ),
+            (
+                Layout::Flat,
+                "bloom",
+                [2991015, 33135, 6660315, 3702438, 744564, 7371753, 4413888],
+            ),
+            (
+                Layout::Partitioned,
+                "hello",
+                [213402, 1587406, 2961411, 4335418, 5709428, 7083442, 8457461],
+            ),
+            (
+                Layout::Partitioned,
+                "world",
+                [996374, 2310930, 3625487, 4940046, 6254608, 7569174, 8883745],
+            ),
+            (
+                Layout::Partitioned,
+                "bloom",
+                [983591, 1699070, 3783844, 4499326, 6584105, 7299594, 9384382],
+            ),
+            (
+                Layout::Blocked,
+                "hello",
+                [
+                    1671652, 1671574, 1671497, 1671422, 1671350, 1671282, 1671219,
+                ],
+            ),
+            (
+                Layout::Blocked,
+                "world",
+                [
+                    5952620, 5952736, 5952853, 5952972, 5952582, 5952708, 5952839,
+                ],
+            ),
+            (
+                Layout::Blocked,
+                "bloom",
+                [
+                    8069793, 8069635, 8069990, 8069835, 8069683, 8070047, 8069904,
+                ],
+            ),
+        ];
+
+        for (layout, entry, positions) in expected {
+            let manager = HashManager::builder()
+                .with_num_of_hash_funcs(7)
+                .with_total_bits(9_585_059)
+                .with_layout(layout)
+                .build()
+                .unwrap();
+
+            assert_eq!(manager.hash(entry).collect::<Vec<_>>(), positions);
+        }
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn should_probe_pinned_positions_past_32_bits() {
+        let manager = HashManager::builder()
+            .with_num_of_hash_funcs(3)
+            .with_total_bits(1 << 40)
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            manager.hash("hello").collect::<Vec<usize>>(),
+            vec![486546741056, 151784679204, 916534245129]
+        );
+    }
+
+    #[test]
+    fn should_compute_pinned_base_hashes_for_built_in_strategies() {
+        let expected: Vec<(Box<dyn Hashing>, (u64, u64))> = vec![
+            (
+                Box::new(DefaultHashingStrategy::default()),
+                (10937164804941551424, 6897222912137252836),
+            ),
+            (
+                Box::new(Xxh3HashingStrategy::default()),
+                (12906435657836328510, 14396560331133893269),
+            ),
+            (
+                Box::new(Murmur3HashingStrategy::default()),
+                (7158103588253421726, 13496678276908549364),
+            ),
+            (
+                Box::new(SipHash13HashingStrategy::default()),
+                (18370697871996211364, 1725871958118937695),
+            ),
+            (
+                Box::new(SeaHashingStrategy::default()),
+                (1921847300542604626, 16077157121176545003),
+            ),
+        ];
+
+        for (strategy, base) in expected {
+            let manager = HashManager::builder()
+                .with_num_of_hash_funcs(1)
+                .with_total_bits(1)
+                .use_strategy(strategy)
+                .build()
+                .unwrap();
+
+            assert_eq!(manager.hash_base("hello"), base);
+        }
     }
 }
@@ -0,0 +1,179 @@
+use super::Layout;
+
+// Positions derives every probe from a single pair of base hashes with enhanced double hashing
+// (Kirsch & Mitzenmacher, Dillinger & Manolios): g(i) = (h1 + i * h2 + (i^3 - i) / 6) mod range,
+// the layout picks the range of bits and the offset it starts at for every probe, a folded filter then
+// wraps every position into its folded bits. All the math is done in u64, so a filter probes the same
+// bits on every platform
+#[derive(Clone)]
+pub(crate) struct Positions {
+    current: u64,
+    step: u64,
+    idx: usize,
+    num_of_hash_funcs: usize,
+    range: u64,
+    offset: u64,
+    offset_step: u64,
+    folded_bits: Option<u64>,
+}
+
+impl Positions {
+    pub(super) fn from(
+        base: (u64, u64),
+        num_of_hash_funcs: usize,
+        total_bits: usize,
+        layout: Layout,
+    ) -> Self {
+        let total_bits = total_bits as u64;
+
+        let (range, offset, offset_step) = match layout {
+            Layout::Flat => (total_bits, 0, 0),
+            Layout::Partitioned => {
+                let slice = total_bits / num_of_hash_funcs as u64;
+                (slice, 0, slice)
+            }
+            Layout::Blocked => {
+                let range = (Layout::BLOCK_BITS as u64).min(total_bits);
+                let block = base.0 % (total_bits / range);
+                (range, block * range, 0)
+            }
+        };
+
+        // A block is chosen by h1, so its probes walk from the halves of h2 to stay independent of it
+        let (first, second) = match layout {
+            Layout::Blocked => (base.1, base.1 >> 32),
+            _ => base,
+        };
+
+        Self {
+            current: first % range,
+            step: second % range,
+            idx: 0,
+            num_of_hash_funcs,
+            range,
+            offset,
+            offset_step,
+            folded_bits: None,
+        }
+    }
+
+    pub(super) fn fold_into(self, folded_bits: usize) -> Self {
+        Self {
+            folded_bits: Some(folded_bits as u64),
+            ..self
+        }
+    }
+}
+
+impl ExactSizeIterator for Positions {}
+
+impl Iterator for Positions {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.idx == self.num_of_hash_funcs {
+            return None;
+        }
+
+        let position = self.offset + self.current;
+
+        self.idx += 1;
+        self.offset += self.offset_step;
+        self.current = (self.current + self.step) % self.range;
+        self.step = (self.step + self.idx as u64) % self.range;
+
+        // Every position is below the total bits of the storage, which always fit in usize
+        match self.folded_bits {
+            Some(folded_bits) => Some((position % folded_bits) as usize),
+            None => Some(position as usize),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.num_of_hash_funcs - self.idx;
+        (remaining, Some(remaining))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Layout, Positions};
+
+    #[test]
+    fn should_follow_enhanced_double_hashing_formula() {
+        let (h1, h2, total_bits) = (123_456_789u64, 987_654_321u64, 1_009u64);
+
+        let positions: Vec<usize> =
+            Positions::from((h1, h2), 10, total_bits as usize, Layout::Flat).collect();
+
+        let expected: Vec<usize> = (0..10u64)
+            .map(|idx| ((h1 + idx * h2 + (idx.pow(3) - idx) / 6) % total_bits) as usize)
+            .collect();
+
+        assert_eq!(positions, expected);
+    }
+
+    #[test]
+    fn should_yield_one_position_per_hash_function() {
+        let positions = Positions::from((u64::MAX, u64::MAX), 7, 64, Layout::Flat);
+
+        assert_eq!(positions.len(), 7);
+        assert!(positions.clone().all(|position| position < 64));
+        assert_eq!(positions.count(), 7);
+    }
+
+    #[test]
+    fn should_reach_positions_past_32_bits() {
+        let total_bits = 1usize << 40;
+
+        let positions: Vec<usize> =
+            Positions::from((u64::MAX - 1, u64::MAX / 7), 7, total_bits, Layout::Flat).collect();
+
+        assert!(positions.iter().all(|&position| position < total_bits));
+        assert!(positions
+            .iter()
+            .all(|&position| position > u32::MAX as usize));
+    }
+
+    #[test]
+    fn should_probe_each_partition_once() {
+        let (num_of_hash_funcs, total_bits) = (7, 7_003);
+        let slice = total_bits / num_of_hash_funcs;
+
+        for base in [(0, 0), (u64::MAX, u64::MAX), (123_456_789, 987_654_321)] {
+            let positions: Vec<usize> =
+                Positions::from(base, num_of_hash_funcs, total_bits, Layout::Partitioned).collect();
+
+            assert_eq!(positions.len(), num_of_hash_funcs);
+            assert!(positions
+                .iter()
+                .enumerate()
+                .all(|(idx, &position)| position / slice == idx));
+        }
+    }
+
+    #[test]
+    fn should_keep_every_probe_inside_one_block() {
+        let total_bits = Layout::BLOCK_BITS * 1_000;
+
+        for base in [(0, 0), (u64::MAX, u64::MAX), (123_456_789, 987_654_321)] {
+            let positions: Vec<usize> =
+                Positions::from(base, 7, total_bits, Layout::Blocked).collect();
+
+            let block = positions[0] / Layout::BLOCK_BITS;
+
+            assert_eq!(positions.len(), 7);
+            assert!(positions
+                .iter()
+                .all(|&position| position / Layout::BLOCK_BITS == block));
+        }
+    }
+
+    #[test]
+    fn should_use_a_single_block_when_total_bits_are_smaller() {
+        let positions = Positions::from((u64::MAX, u64::MAX), 7, 100, Layout::Blocked);
+
+        assert!(positions.clone().all(|position| position < 100));
+        assert_eq!(positions.count(), 7);
+    }
+}
@@ -1,16 +1,80 @@
-use fasthash::murmur;
+use fasthash::{murmur2::Hash64_x64, FastHash};
 use fnv::FnvHasher;
 use std::hash::Hasher;
 
+pub use murmur3::Murmur3HashingStrategy;
+pub use sea::SeaHashingStrategy;
+pub use sip::SipHash13HashingStrategy;
+pub use xxh3::Xxh3HashingStrategy;
+
+pub(crate) use utility::remix;
+
 use super::seed::Seed;
 
-pub trait Hashing {
-    fn hash(&self, idx: usize, total_bits: usize, entry: &dyn ToString) -> usize;
+mod murmur3;
+mod sea;
+mod sip;
+mod utility;
+mod xxh3;
+
+pub const CUSTOM_STRATEGY_IDENTIFIER: u8 = u8::MAX;
+
+// Custom strategies keep the reserved identifier, a filter hashed with one can be used but not persisted
+pub trait Hashing: Send + Sync {
+    fn hash(&self, entry: &[u8]) -> (u64, u64);
+
+    fn get_identifier(&self) -> u8 {
+        CUSTOM_STRATEGY_IDENTIFIER
+    }
+
+    fn get_seed(&self) -> u64 {
+        0
+    }
 }
 
-// DefaultHashingStrategy uses double hasing strategy, combining two hash functions: murmurhash, fvnl hash
-// Formula: H(idx, hM, hF) = (hM + idx * hF  + idx * idx) mod total_bits
-pub(crate) struct DefaultHashingStrategy(Seed);
+pub(crate) fn from_identifier(identifier: u8, seed: u64) -> Option<Box<dyn Hashing>> {
+    match identifier {
+        DefaultHashingStrategy::IDENTIFIER => {
+            Some(Box::new(DefaultHashingStrategy::from(Seed::from(seed))))
+        }
+        Xxh3HashingStrategy::IDENTIFIER => {
+            Some(Box::new(Xxh3HashingStrategy::from(Seed::from(seed))))
+        }
+        Murmur3HashingStrategy::IDENTIFIER => {
+            Some(Box::new(Murmur3HashingStrategy::from(Seed::from(seed))))
+        }
+        SipHash13HashingStrategy::IDENTIFIER => {
+            Some(Box::new(SipHash13HashingStrategy::from(Seed::from(seed))))
+        }
+        SeaHashingStrategy::IDENTIFIER => {
+            Some(Box::new(SeaHashingStrategy::from(Seed::from(seed))))
+        }
+        _ => None,
+    }
+}
+
+pub(crate) fn name_of(identifier: u8) -> &'static str {
+    match identifier {
+        DefaultHashingStrategy::IDENTIFIER => "murmur2-fnv",
+        Xxh3HashingStrategy::IDENTIFIER => "xxh3-64",
+        Murmur3HashingStrategy::IDENTIFIER => "murmur3-x64-128",
+        SipHash13HashingStrategy::IDENTIFIER => "siphash-1-3",
+        SeaHashingStrategy::IDENTIFIER => "seahash",
+        _ => "custom",
+    }
+}
+
+// DefaultHashingStrategy combines two 64-bit hash functions into the base pair of double hashing: murmurhash2, fnv1 hash
+#[derive(Default)]
+pub struct DefaultHashingStrategy(Seed);
+
+impl DefaultHashingStrategy {
+    const IDENTIFIER: u8 = 0;
+
+    pub fn with_seed(seed: u64) -> Self {
+        Self(Seed::from(seed))
+    }
+}
 
 impl From<Seed> for DefaultHashingStrategy {
     fn from(value: Seed) -> Self {
@@ -19,35 +83,116 @@ impl From<Seed> for DefaultHashingStrategy {
 }
 
 impl Hashing for DefaultHashingStrategy {
-    fn hash(&self, idx: usize, total_bits: usize, entry: &dyn ToString) -> usize {
-        let murmurhash_value = murmur::hash32(entry.to_string()) as usize;
+    fn hash(&self, entry: &[u8]) -> (u64, u64) {
+        let murmurhash_value = Hash64_x64::hash(entry);
 
         let fnvlhash_value = {
             let mut hasher = FnvHasher::with_key(self.0.get_seed());
-            hasher.write(entry.to_string().as_bytes());
-            hasher.finish() as usize
+            hasher.write(entry);
+            hasher.finish()
         };
 
-        let mut hashed_value = murmurhash_value;
+        (murmurhash_value, fnvlhash_value)
+    }
+
+    fn get_identifier(&self) -> u8 {
+        Self::IDENTIFIER
+    }
+
+    fn get_seed(&self) -> u64 {
+        self.0.get_seed()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        from_identifier, DefaultHashingStrategy, Hashing, Murmur3HashingStrategy,
+        SeaHashingStrategy, SipHash13HashingStrategy, Xxh3HashingStrategy,
+        CUSTOM_STRATEGY_IDENTIFIER,
+    };
+    use crate::bloom_filters::hashing::seed::Seed;
+
+    #[test]
+    fn should_keep_identifiers_of_built_in_strategies_stable() {
+        let strategies: Vec<(Box<dyn Hashing>, u8)> = vec![
+            (Box::new(DefaultHashingStrategy::default()), 0),
+            (Box::new(Xxh3HashingStrategy::default()), 1),
+            (Box::new(Murmur3HashingStrategy::default()), 2),
+            (Box::new(SipHash13HashingStrategy::default()), 3),
+            (Box::new(SeaHashingStrategy::default()), 4),
+        ];
+
+        for (strategy, expected_identifier) in strategies {
+            assert_eq!(strategy.get_identifier(), expected_identifier);
 
-        let (result, overflowed) = idx.overflowing_mul(fnvlhash_value);
+            let restored = from_identifier(expected_identifier, 41).unwrap();
+            let reseeded = from_identifier(expected_identifier, strategy.get_seed()).unwrap();
 
-        if !overflowed {
-            hashed_value = hashed_value.wrapping_add(result);
-        } else {
-            let remaining = (idx as u128 * fnvlhash_value as u128) % (usize::MAX as u128 + 1);
-            hashed_value = hashed_value.wrapping_add(remaining as usize);
+            assert_eq!(restored.get_identifier(), expected_identifier);
+            assert_eq!(restored.get_seed(), 41);
+            assert_eq!(reseeded.hash(b"hello"), strategy.hash(b"hello"));
         }
+    }
 
-        let (result, overflowed) = idx.overflowing_mul(idx);
+    #[test]
+    fn should_hash_deterministically_and_differently_with_another_seed() {
+        let strategies: Vec<(Box<dyn Hashing>, Box<dyn Hashing>)> = vec![
+            (
+                Box::new(DefaultHashingStrategy::default()),
+                Box::new(DefaultHashingStrategy::with_seed(37)),
+            ),
+            (
+                Box::new(Xxh3HashingStrategy::default()),
+                Box::new(Xxh3HashingStrategy::with_seed(37)),
+            ),
+            (
+                Box::new(Murmur3HashingStrategy::default()),
+                Box::new(Murmur3HashingStrategy::with_seed(37)),
+            ),
+            (
+                Box::new(SipHash13HashingStrategy::default()),
+                Box::new(SipHash13HashingStrategy::with_seed(37)),
+            ),
+            (
+                Box::new(SeaHashingStrategy::default()),
+                Box::new(SeaHashingStrategy::with_seed(37)),
+            ),
+        ];
 
-        if !overflowed {
-            hashed_value = hashed_value.wrapping_add(result);
-        } else {
-            let remaining = (idx as u128 * idx as u128) % (usize::MAX as u128 + 1);
-            hashed_value = hashed_value.wrapping_add(remaining as usize);
+        for (strategy, reseeded) in strategies {
+            assert_eq!(strategy.hash(b"hello"), strategy.hash(b"hello"));
+            assert_ne!(strategy.hash(b"hello"), strategy.hash(b"world"));
+            assert_ne!(strategy.hash(b"hello"), reseeded.hash(b"hello"));
+            assert_eq!(reseeded.get_seed(), 37);
         }
+    }
+
+    #[test]
+    fn should_restore_default_strategy_from_its_identifier() {
+        let strategy = DefaultHashingStrategy::from(Seed::from(37));
+
+        let restored = from_identifier(strategy.get_identifier(), strategy.get_seed()).unwrap();
+
+        assert_eq!(restored.get_identifier(), strategy.get_identifier());
+        assert_eq!(restored.get_seed(), 37);
+        assert_eq!(restored.hash(b"hello"), strategy.hash(b"hello"));
+    }
+
+    #[test]
+    fn should_spread_default_base_hashes_over_64_bits() {
+        let strategy = DefaultHashingStrategy::default();
+
+        let base_hashes: Vec<(u64, u64)> = (0..64u32)
+            .map(|idx| strategy.hash(&idx.to_le_bytes()))
+            .collect();
+
+        assert!(base_hashes.iter().any(|(h1, _)| *h1 > u64::from(u32::MAX)));
+        assert!(base_hashes.iter().any(|(_, h2)| *h2 > u64::from(u32::MAX)));
+    }
 
-        hashed_value % total_bits
+    #[test]
+    fn should_return_none_for_unknown_identifier() {
+        assert!(from_identifier(CUSTOM_STRATEGY_IDENTIFIER, 29).is_none());
     }
 }
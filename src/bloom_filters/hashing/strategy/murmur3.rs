@@ -0,0 +1,40 @@
+use fasthash::{murmur3::Hash128_x64, FastHash};
+
+use super::{Hashing, Seed};
+
+// Murmur3HashingStrategy splits the 128-bit x64 variant of MurmurHash3 into the two base hashes.
+// MurmurHash3 only takes a 32-bit seed, so both halves of the seed are xored together and seeds whose halves
+// xor to the same value hash alike
+#[derive(Default)]
+pub struct Murmur3HashingStrategy(Seed);
+
+impl Murmur3HashingStrategy {
+    pub(super) const IDENTIFIER: u8 = 2;
+
+    pub fn with_seed(seed: u64) -> Self {
+        Self(Seed::from(seed))
+    }
+}
+
+impl From<Seed> for Murmur3HashingStrategy {
+    fn from(value: Seed) -> Self {
+        Self(value)
+    }
+}
+
+impl Hashing for Murmur3HashingStrategy {
+    fn hash(&self, entry: &[u8]) -> (u64, u64) {
+        let seed = self.0.get_seed();
+        let hashed_value = Hash128_x64::hash_with_seed(entry, (seed ^ (seed >> 32)) as u32);
+
+        (hashed_value as u64, (hashed_value >> 64) as u64)
+    }
+
+    fn get_identifier(&self) -> u8 {
+        Self::IDENTIFIER
+    }
+
+    fn get_seed(&self) -> u64 {
+        self.0.get_seed()
+    }
+}
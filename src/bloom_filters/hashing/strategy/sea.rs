@@ -0,0 +1,38 @@
+use seahash::hash_seeded;
+
+use super::{utility::remix, Hashing, Seed};
+
+// SeaHashingStrategy hashes with SeaHash, keyed by the seed, and remixes it into the second base hash
+#[derive(Default)]
+pub struct SeaHashingStrategy(Seed);
+
+impl SeaHashingStrategy {
+    pub(super) const IDENTIFIER: u8 = 4;
+
+    pub fn with_seed(seed: u64) -> Self {
+        Self(Seed::from(seed))
+    }
+}
+
+impl From<Seed> for SeaHashingStrategy {
+    fn from(value: Seed) -> Self {
+        Self(value)
+    }
+}
+
+impl Hashing for SeaHashingStrategy {
+    fn hash(&self, entry: &[u8]) -> (u64, u64) {
+        let seed = self.0.get_seed();
+        let hashed_value = hash_seeded(entry, seed, remix(seed), remix(!seed), !seed);
+
+        (hashed_value, remix(hashed_value))
+    }
+
+    fn get_identifier(&self) -> u8 {
+        Self::IDENTIFIER
+    }
+
+    fn get_seed(&self) -> u64 {
+        self.0.get_seed()
+    }
+}
@@ -0,0 +1,42 @@
+use std::hash::Hasher;
+
+use siphasher::sip::SipHasher13;
+
+use super::{utility::remix, Hashing, Seed};
+
+// SipHash13HashingStrategy hashes with SipHash-1-3, keyed by the seed, and remixes it into the second base hash
+#[derive(Default)]
+pub struct SipHash13HashingStrategy(Seed);
+
+impl SipHash13HashingStrategy {
+    pub(super) const IDENTIFIER: u8 = 3;
+
+    pub fn with_seed(seed: u64) -> Self {
+        Self(Seed::from(seed))
+    }
+}
+
+impl From<Seed> for SipHash13HashingStrategy {
+    fn from(value: Seed) -> Self {
+        Self(value)
+    }
+}
+
+impl Hashing for SipHash13HashingStrategy {
+    fn hash(&self, entry: &[u8]) -> (u64, u64) {
+        let seed = self.0.get_seed();
+        let mut hasher = SipHasher13::new_with_keys(seed, remix(seed));
+        hasher.write(entry);
+        let hashed_value = hasher.finish();
+
+        (hashed_value, remix(hashed_value))
+    }
+
+    fn get_identifier(&self) -> u8 {
+        Self::IDENTIFIER
+    }
+
+    fn get_seed(&self) -> u64 {
+        self.0.get_seed()
+    }
+}
@@ -0,0 +1,22 @@
+// Finalizer of splitmix64, derives a second well spread 64-bit hash from the first one
+pub(crate) fn remix(value: u64) -> u64 {
+    let mut value = value.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    value = (value ^ (value >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    value = (value ^ (value >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    value ^ (value >> 31)
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn should_remix_into_different_values() {
+        let test_cases = vec![0, 1, 29, u64::MAX];
+
+        for value in test_cases {
+            assert_ne!(super::remix(value), value);
+            assert_eq!(super::remix(value), super::remix(value));
+        }
+
+        assert_ne!(super::remix(1), super::remix(2));
+    }
+}
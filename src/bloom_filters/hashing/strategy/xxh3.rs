@@ -0,0 +1,37 @@
+use xxhash_rust::xxh3::xxh3_64_with_seed;
+
+use super::{utility::remix, Hashing, Seed};
+
+// Xxh3HashingStrategy hashes with the 64-bit xxHash3 and remixes it into the second base hash
+#[derive(Default)]
+pub struct Xxh3HashingStrategy(Seed);
+
+impl Xxh3HashingStrategy {
+    pub(super) const IDENTIFIER: u8 = 1;
+
+    pub fn with_seed(seed: u64) -> Self {
+        Self(Seed::from(seed))
+    }
+}
+
+impl From<Seed> for Xxh3HashingStrategy {
+    fn from(value: Seed) -> Self {
+        Self(value)
+    }
+}
+
+impl Hashing for Xxh3HashingStrategy {
+    fn hash(&self, entry: &[u8]) -> (u64, u64) {
+        let hashed_value = xxh3_64_with_seed(entry, self.0.get_seed());
+
+        (hashed_value, remix(hashed_value))
+    }
+
+    fn get_identifier(&self) -> u8 {
+        Self::IDENTIFIER
+    }
+
+    fn get_seed(&self) -> u64 {
+        self.0.get_seed()
+    }
+}
@@ -0,0 +1,302 @@
+use std::fmt;
+
+use super::{Layout, SupportedFloatingPointType};
+
+// Inspection is a snapshot of the parameters and the state of a filter at the time it was inspected
+pub struct Inspection {
+    pub(super) total_bits: usize,
+    pub(super) size_in_bytes: usize,
+    pub(super) num_of_hash_funcs: usize,
+    pub(super) seed: u64,
+    pub(super) strategy: &'static str,
+    pub(super) layout: Layout,
+    pub(super) fold_factor: usize,
+    pub(super) max_size: usize,
+    pub(super) max_tolerance: SupportedFloatingPointType,
+    pub(super) unique_entry_count: usize,
+    pub(super) estimated_unique_entry_count: SupportedFloatingPointType,
+    pub(super) set_bits: usize,
+    pub(super) estimated_false_positive_rate: SupportedFloatingPointType,
+    pub(super) regions: Vec<(usize, usize)>,
+}
+
+impl Inspection {
+    pub(super) const NUM_OF_REGIONS: usize = 16;
+    const BAR_WIDTH: usize = 32;
+
+    pub fn get_total_bits(&self) -> usize {
+        self.total_bits
+    }
+
+    pub fn get_size_in_bytes(&self) -> usize {
+        self.size_in_bytes
+    }
+
+    pub fn get_num_of_hash_funcs(&self) -> usize {
+        self.num_of_hash_funcs
+    }
+
+    pub fn get_seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn get_strategy(&self) -> &'static str {
+        self.strategy
+    }
+
+    pub fn get_layout(&self) -> Layout {
+        self.layout
+    }
+
+    pub fn get_fold_factor(&self) -> usize {
+        self.fold_factor
+    }
+
+    pub fn get_max_size(&self) -> usize {
+        self.max_size
+    }
+
+    pub fn get_max_tolerance(&self) -> SupportedFloatingPointType {
+        self.max_tolerance
+    }
+
+    pub fn get_unique_entry_count(&self) -> usize {
+        self.unique_entry_count
+    }
+
+    pub fn get_estimated_unique_entry_count(&self) -> SupportedFloatingPointType {
+        self.estimated_unique_entry_count
+    }
+
+    pub fn get_set_bits(&self) -> usize {
+        self.set_bits
+    }
+
+    pub fn get_estimated_false_positive_rate(&self) -> SupportedFloatingPointType {
+        self.estimated_false_positive_rate
+    }
+
+    pub fn fill_ratio(&self) -> SupportedFloatingPointType {
+        self.set_bits as SupportedFloatingPointType / self.total_bits as SupportedFloatingPointType
+    }
+
+    pub fn fill_ratio_per_region(&self) -> Vec<SupportedFloatingPointType> {
+        self.regions
+            .iter()
+            .map(|&(set_bits, region_bits)| {
+                set_bits as SupportedFloatingPointType / region_bits as SupportedFloatingPointType
+            })
+            .collect()
+    }
+
+    // Every value is a number or one of the fixed names of strategies and layouts, so nothing needs escaping
+    pub fn to_json(&self) -> String {
+        let regions = self
+            .fill_ratio_per_region()
+            .into_iter()
+            .map(Self::to_json_number)
+            .collect::<Vec<_>>()
+            .join(",");
+
+        format!(
+            concat!(
+                "{{\"total_bits\":{},\"size_in_bytes\":{},\"num_of_hash_funcs\":{},\"seed\":{},",
+                "\"strategy\":\"{}\",\"layout\":\"{:?}\",\"fold_factor\":{},\"max_size\":{},",
+                "\"max_tolerance\":{},\"unique_entry_count\":{},\"estimated_unique_entry_count\":{},",
+                "\"set_bits\":{},\"fill_ratio\":{},\"estimated_false_positive_rate\":{},",
+                "\"fill_ratio_per_region\":[{}]}}"
+            ),
+            self.total_bits,
+            self.size_in_bytes,
+            self.num_of_hash_funcs,
+            self.seed,
+            self.strategy,
+            self.layout,
+            self.fold_factor,
+            self.max_size,
+            Self::to_json_number(self.max_tolerance),
+            self.unique_entry_count,
+            Self::to_json_number(self.estimated_unique_entry_count),
+            self.set_bits,
+            Self::to_json_number(self.fill_ratio()),
+            Self::to_json_number(self.estimated_false_positive_rate),
+            regions
+        )
+    }
+
+    // JSON has no infinity or NaN, a saturated filter estimates infinitely many entries
+    fn to_json_number(value: SupportedFloatingPointType) -> String {
+        match value.is_finite() {
+            true => value.to_string(),
+            false => String::from("null"),
+        }
+    }
+}
+
+impl fmt::Display for Inspection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Bloom filter")?;
+        writeln!(
+            f,
+            "  total bits           {} ({} bytes)",
+            self.total_bits, self.size_in_bytes
+        )?;
+        writeln!(f, "  hash functions       {}", self.num_of_hash_funcs)?;
+        writeln!(
+            f,
+            "  strategy             {} (seed {})",
+            self.strategy, self.seed
+        )?;
+        writeln!(
+            f,
+            "  layout               {:?} (folded {}x)",
+            self.layout, self.fold_factor
+        )?;
+        writeln!(
+            f,
+            "  capacity             {} at tolerance {}",
+            self.max_size, self.max_tolerance
+        )?;
+        writeln!(
+            f,
+            "  entries              {} inserted, {:.0} estimated",
+            self.unique_entry_count, self.estimated_unique_entry_count
+        )?;
+        writeln!(
+            f,
+            "  set bits             {} (fill ratio {:.4})",
+            self.set_bits,
+            self.fill_ratio()
+        )?;
+        writeln!(
+            f,
+            "  false positive rate  {:.6} estimated",
+            self.estimated_false_positive_rate
+        )?;
+        writeln!(f, "  fill ratio per region")?;
+
+        self.fill_ratio_per_region()
+            .iter()
+            .enumerate()
+            .try_for_each(|(region, ratio)| {
+                let filled =
+                    (ratio * Self::BAR_WIDTH as SupportedFloatingPointType).round() as usize;
+
+                writeln!(
+                    f,
+                    "    {:>3} {}{} {:.4}",
+                    region,
+                    "#".repeat(filled),
+                    ".".repeat(Self::BAR_WIDTH - filled),
+                    ratio
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::bloom_filters::{
+        BloomFilter, Layout, SupportedFloatingPointType, Xxh3HashingStrategy,
+    };
+
+    fn sample_filter() -> BloomFilter<u64> {
+        let filter = BloomFilter::builder()
+            .with_max_size(1_000)
+            .with_total_bits(8_192)
+            .with_num_of_hash_funcs(4)
+            .with_layout(Layout::Partitioned)
+            .with_strategy(Box::new(Xxh3HashingStrategy::with_seed(7)))
+            .build()
+            .unwrap();
+
+        filter.insert_many(&(0..500).collect::<Vec<_>>());
+
+        filter
+    }
+
+    #[test]
+    fn should_report_parameters_and_state() {
+        let inspection = sample_filter().inspect();
+
+        assert_eq!(inspection.get_total_bits(), 8_192);
+        assert_eq!(inspection.get_size_in_bytes(), 1_024);
+        assert_eq!(inspection.get_num_of_hash_funcs(), 4);
+        assert_eq!(inspection.get_seed(), 7);
+        assert_eq!(inspection.get_strategy(), "xxh3-64");
+        assert_eq!(inspection.get_layout(), Layout::Partitioned);
+        assert_eq!(inspection.get_fold_factor(), 1);
+        assert_eq!(inspection.get_max_size(), 1_000);
+        assert!((490..=500).contains(&inspection.get_unique_entry_count()));
+        assert!((inspection.get_estimated_unique_entry_count() - 500.0).abs() < 25.0);
+        assert!(inspection.get_set_bits() <= 2_000);
+        assert!((inspection.fill_ratio() - 0.22).abs() < 0.02);
+        assert!(
+            (inspection.get_estimated_false_positive_rate() - inspection.fill_ratio().powi(4))
+                .abs()
+                < 1e-9
+        );
+    }
+
+    #[test]
+    fn should_report_fill_ratio_of_every_region() {
+        let inspection = sample_filter().inspect();
+        let regions = inspection.fill_ratio_per_region();
+
+        assert_eq!(regions.len(), 16);
+        assert!(regions.iter().all(|ratio| (ratio - 0.22).abs() < 0.1));
+
+        let mean = regions.iter().sum::<f64>() / regions.len() as f64;
+        assert!((mean - inspection.fill_ratio()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn should_report_every_region_of_a_tiny_filter() {
+        let filter: BloomFilter = BloomFilter::builder()
+            .with_max_size(1)
+            .with_total_bits(12)
+            .build()
+            .unwrap();
+
+        assert_eq!(filter.inspect().fill_ratio_per_region(), vec![0.0, 0.0]);
+    }
+
+    #[test]
+    fn should_render_human_and_machine_readable_forms() {
+        let inspection = sample_filter().inspect();
+
+        let text = inspection.to_string();
+        assert!(text.contains("total bits           8192 (1024 bytes)"));
+        assert!(text.contains("strategy             xxh3-64 (seed 7)"));
+        assert_eq!(text.lines().count(), 10 + 16);
+
+        let json: serde_json::Value = serde_json::from_str(&inspection.to_json()).unwrap();
+        assert_eq!(json["total_bits"], 8192);
+        assert_eq!(json["size_in_bytes"], 1024);
+        assert_eq!(json["strategy"], "xxh3-64");
+        assert_eq!(json["layout"], "Partitioned");
+        assert_eq!(json.as_object().unwrap().len(), 15);
+        assert_eq!(json["fill_ratio_per_region"].as_array().unwrap().len(), 16);
+    }
+
+    #[test]
+    fn should_render_non_finite_estimates_as_null_json() {
+        let mut inspection = sample_filter().inspect();
+        inspection.estimated_unique_entry_count = SupportedFloatingPointType::INFINITY;
+        inspection.estimated_false_positive_rate = SupportedFloatingPointType::NAN;
+
+        let json: serde_json::Value = serde_json::from_str(&inspection.to_json()).unwrap();
+        assert!(json["estimated_unique_entry_count"].is_null());
+        assert!(json["estimated_false_positive_rate"].is_null());
+        assert_eq!(json["set_bits"], inspection.get_set_bits());
+    }
+
+    #[test]
+    fn should_describe_filter_for_debugging() {
+        let debug = format!("{:?}", sample_filter());
+
+        assert!(debug.starts_with("BloomFilter { configuration: Configuration {"));
+        assert!(debug.contains("total_bits: 8192"));
+        assert!(debug.contains("strategy: \"xxh3-64\""));
+    }
+}
@@ -0,0 +1,184 @@
+use std::{fs::File, hash::Hash, marker::PhantomData};
+
+use memmap2::Mmap;
+
+use super::{
+    configurations::{Configurable, Configuration, Limits},
+    hashing::HashManager,
+    persistence::Header,
+    restore_from,
+    storage::MappedStorage,
+    BloomFilterError, Layout, SupportedFloatingPointType,
+};
+
+// MappedBloomFilter answers lookups from a persisted filter mapped into memory, processes opening
+// the same file share its pages and nothing is copied, so the filter cannot take new entries
+pub struct MappedBloomFilter<T: ?Sized = str> {
+    configuration: Configuration,
+    manager: HashManager,
+    storage: MappedStorage,
+    entry: PhantomData<fn(&T)>,
+}
+
+impl<T: Hash + ?Sized> MappedBloomFilter<T> {
+    pub fn open(path: &str) -> Result<Self, BloomFilterError> {
+        let file = File::open(path)
+            .map_err(|_| BloomFilterError::Persistence(format!("Cannot open file at {}", path)))?;
+
+        // SAFETY: the mapping stays valid only while no one truncates or writes to the file, which callers rely on
+        // by replacing persisted filters with a new file instead of rewriting them in place
+        let map = unsafe { Mmap::map(&file) }
+            .map_err(|_| BloomFilterError::Persistence(format!("Cannot map file at {}", path)))?;
+
+        let mut bytes = &map[..];
+        let header = Header::read_from(&mut bytes)
+            .map_err(String::from)
+            .map_err(BloomFilterError::Persistence)?;
+        let offset = map.len() - bytes.len();
+
+        let (configuration, manager) = restore_from(&header, &Limits::unbounded())?;
+
+        let storage = MappedStorage::try_from(map, offset, configuration.get_total_bits())
+            .map_err(String::from)
+            .map_err(BloomFilterError::Persistence)?;
+
+        Ok(Self {
+            configuration,
+            manager,
+            storage,
+            entry: PhantomData,
+        })
+    }
+
+    pub fn contains(&self, entry: &T) -> bool {
+        self.manager
+            .hash(entry)
+            .all(|idx| self.storage.read_bit_at(idx) != 0)
+    }
+
+    pub fn get_total_bits(&self) -> usize {
+        self.configuration.get_total_bits()
+    }
+
+    pub fn get_num_of_hash_funcs(&self) -> usize {
+        self.manager.get_num_of_hash_funcs()
+    }
+
+    pub fn get_layout(&self) -> Layout {
+        self.manager.get_layout()
+    }
+
+    pub fn get_unique_entry_count(&self) -> usize {
+        self.configuration.get_unique_entry_count()
+    }
+
+    pub fn get_size_in_bytes(&self) -> usize {
+        self.storage.get_size_in_bytes()
+    }
+
+    pub fn expected_false_positive_rate(&self) -> SupportedFloatingPointType {
+        self.configuration.get_expected_false_positive_rate()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use super::MappedBloomFilter;
+    use crate::bloom_filters::{ApproximateMembership, BloomFilter, BloomFilterError, Layout};
+
+    fn save_sample_filter(name: &str) -> String {
+        let path = std::env::temp_dir().join(name);
+        let path = path.to_str().unwrap().to_string();
+
+        let filter: BloomFilter = BloomFilter::builder()
+            .with_max_size(1_000)
+            .with_layout(Layout::Blocked)
+            .build()
+            .unwrap();
+        filter.insert_many(["hello", "world"]);
+        filter.save(&path).unwrap();
+
+        path
+    }
+
+    #[test]
+    fn should_answer_lookups_from_mapped_file() {
+        let path = save_sample_filter("mapped_should_answer_lookups_from_mapped_file.bin");
+
+        let filter = MappedBloomFilter::<str>::open(&path).unwrap();
+        let restored = BloomFilter::<str>::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert!(filter.contains("hello"));
+        assert!(filter.contains("world"));
+        assert!(!filter.contains("bloom"));
+        assert_eq!(filter.get_layout(), Layout::Blocked);
+        assert_eq!(filter.get_total_bits(), restored.get_total_bits());
+        assert_eq!(
+            filter.get_num_of_hash_funcs(),
+            restored.get_num_of_hash_funcs()
+        );
+        assert_eq!(filter.get_unique_entry_count(), 2);
+        assert_eq!(
+            filter.get_size_in_bytes(),
+            restored.get_total_bits().div_ceil(8)
+        );
+    }
+
+    #[test]
+    fn should_return_err_when_file_length_does_not_match_header() {
+        let path = save_sample_filter("mapped_should_return_err_when_file_length_is_wrong.bin");
+
+        let mut bytes = fs::read(&path).unwrap();
+        bytes.pop();
+        fs::write(&path, &bytes).unwrap();
+        let truncated = MappedBloomFilter::<str>::open(&path);
+
+        bytes.extend_from_slice(&[0, 0]);
+        fs::write(&path, &bytes).unwrap();
+        let extended = MappedBloomFilter::<str>::open(&path);
+
+        fs::remove_file(&path).unwrap();
+
+        assert!(truncated.is_err());
+        assert!(extended.is_err());
+    }
+
+    #[test]
+    fn should_return_err_when_header_is_invalid() {
+        let path = save_sample_filter("mapped_should_return_err_when_header_is_invalid.bin");
+
+        let mut bytes = fs::read(&path).unwrap();
+        bytes[0] = b'X';
+        fs::write(&path, &bytes).unwrap();
+        let wrong_magic = MappedBloomFilter::<str>::open(&path);
+
+        fs::write(&path, &bytes[..10]).unwrap();
+        let truncated_header = MappedBloomFilter::<str>::open(&path);
+
+        fs::remove_file(&path).unwrap();
+
+        assert!(wrong_magic.is_err());
+        assert!(truncated_header.is_err());
+        assert!(MappedBloomFilter::<str>::open(&path).is_err());
+    }
+
+    #[test]
+    fn should_reject_inserts() {
+        let path = save_sample_filter("mapped_should_reject_inserts.bin");
+
+        let filter: Box<dyn ApproximateMembership> =
+            Box::new(MappedBloomFilter::<str>::open(&path).unwrap());
+        fs::remove_file(&path).unwrap();
+
+        assert!(matches!(
+            filter.insert("bloom"),
+            Err(BloomFilterError::Unsupported(_))
+        ));
+        assert!(filter.insert_all(&["bloom"]).is_err());
+        assert!(!filter.contains("bloom"));
+        assert_eq!(filter.len(), Some(2));
+    }
+}
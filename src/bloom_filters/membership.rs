@@ -0,0 +1,333 @@
+use std::hash::Hash;
+
+use super::{
+    AgingBloomFilter, BloomFilter, BloomFilterError, CountingBloomFilter, CuckooFilter,
+    MappedBloomFilter, ScalableBloomFilter, StableBloomFilter, SupportedFloatingPointType,
+    XorFilter,
+};
+
+// ApproximateMembership is what the use cases need from a filter, the optional parts default to unsupported
+pub trait ApproximateMembership<T: ?Sized = str>: Send + Sync {
+    fn insert(&self, entry: &T) -> Result<(), BloomFilterError>;
+
+    fn contains(&self, entry: &T) -> bool;
+
+    fn insert_all(&self, entries: &[&T]) -> Result<(), BloomFilterError> {
+        entries.iter().try_for_each(|entry| self.insert(entry))
+    }
+
+    fn remove(&self, _entry: &T) -> Result<bool, BloomFilterError> {
+        Err(BloomFilterError::Unsupported(String::from(
+            "Filter cannot remove entries",
+        )))
+    }
+
+    fn len(&self) -> Option<usize> {
+        None
+    }
+
+    fn is_empty(&self) -> Option<bool> {
+        self.len().map(|len| len == 0)
+    }
+
+    fn false_positive_rate(&self) -> Option<SupportedFloatingPointType> {
+        None
+    }
+
+    fn save(&self, _path: &str) -> Result<(), BloomFilterError> {
+        Err(BloomFilterError::Unsupported(String::from(
+            "Filter cannot be saved",
+        )))
+    }
+}
+
+impl<T: Hash + ?Sized> ApproximateMembership<T> for BloomFilter<T> {
+    fn insert(&self, entry: &T) -> Result<(), BloomFilterError> {
+        BloomFilter::insert(self, entry);
+        Ok(())
+    }
+
+    fn contains(&self, entry: &T) -> bool {
+        BloomFilter::contains(self, entry)
+    }
+
+    fn insert_all(&self, entries: &[&T]) -> Result<(), BloomFilterError> {
+        self.insert_many(entries.iter().copied());
+        Ok(())
+    }
+
+    fn len(&self) -> Option<usize> {
+        Some(self.estimate_unique_entry_count().round() as usize)
+    }
+
+    fn false_positive_rate(&self) -> Option<SupportedFloatingPointType> {
+        Some(self.estimate_false_positive_rate())
+    }
+
+    fn save(&self, path: &str) -> Result<(), BloomFilterError> {
+        BloomFilter::save(self, path)
+    }
+}
+
+impl<T: Hash + ?Sized> ApproximateMembership<T> for CountingBloomFilter<T> {
+    fn insert(&self, entry: &T) -> Result<(), BloomFilterError> {
+        CountingBloomFilter::insert(self, entry);
+        Ok(())
+    }
+
+    fn contains(&self, entry: &T) -> bool {
+        CountingBloomFilter::contains(self, entry)
+    }
+
+    fn remove(&self, entry: &T) -> Result<bool, BloomFilterError> {
+        Ok(CountingBloomFilter::remove(self, entry))
+    }
+}
+
+impl<T: Hash + ?Sized> ApproximateMembership<T> for ScalableBloomFilter<T> {
+    fn insert(&self, entry: &T) -> Result<(), BloomFilterError> {
+        ScalableBloomFilter::insert(self, entry)
+    }
+
+    fn contains(&self, entry: &T) -> bool {
+        ScalableBloomFilter::contains(self, entry)
+    }
+}
+
+impl<T: Hash + ?Sized> ApproximateMembership<T> for StableBloomFilter<T> {
+    fn insert(&self, entry: &T) -> Result<(), BloomFilterError> {
+        StableBloomFilter::insert(self, entry);
+        Ok(())
+    }
+
+    fn contains(&self, entry: &T) -> bool {
+        StableBloomFilter::contains(self, entry)
+    }
+
+    fn false_positive_rate(&self) -> Option<SupportedFloatingPointType> {
+        Some(self.stable_false_positive_rate())
+    }
+}
+
+impl<T: Hash + ?Sized> ApproximateMembership<T> for AgingBloomFilter<T> {
+    fn insert(&self, entry: &T) -> Result<(), BloomFilterError> {
+        AgingBloomFilter::insert(self, entry);
+        Ok(())
+    }
+
+    fn contains(&self, entry: &T) -> bool {
+        AgingBloomFilter::contains(self, entry)
+    }
+}
+
+impl<T: Hash + ?Sized> ApproximateMembership<T> for CuckooFilter<T> {
+    fn insert(&self, entry: &T) -> Result<(), BloomFilterError> {
+        CuckooFilter::insert(self, entry)
+    }
+
+    fn contains(&self, entry: &T) -> bool {
+        CuckooFilter::contains(self, entry)
+    }
+
+    fn remove(&self, entry: &T) -> Result<bool, BloomFilterError> {
+        Ok(CuckooFilter::remove(self, entry))
+    }
+
+    fn len(&self) -> Option<usize> {
+        Some(CuckooFilter::len(self))
+    }
+
+    fn false_positive_rate(&self) -> Option<SupportedFloatingPointType> {
+        Some(self.expected_false_positive_rate())
+    }
+}
+
+impl<T: Hash + ?Sized> ApproximateMembership<T> for XorFilter<T> {
+    fn insert(&self, _entry: &T) -> Result<(), BloomFilterError> {
+        Err(BloomFilterError::Unsupported(String::from(
+            "Static filter cannot insert entries after being built",
+        )))
+    }
+
+    fn contains(&self, entry: &T) -> bool {
+        XorFilter::contains(self, entry)
+    }
+
+    fn insert_all(&self, entries: &[&T]) -> Result<(), BloomFilterError> {
+        match entries.first() {
+            Some(entry) => ApproximateMembership::insert(self, entry),
+            None => Ok(()),
+        }
+    }
+
+    fn len(&self) -> Option<usize> {
+        Some(self.get_unique_entry_count())
+    }
+
+    fn false_positive_rate(&self) -> Option<SupportedFloatingPointType> {
+        Some(self.expected_false_positive_rate())
+    }
+
+    fn save(&self, path: &str) -> Result<(), BloomFilterError> {
+        XorFilter::save(self, path)
+    }
+}
+
+impl<T: Hash + ?Sized> ApproximateMembership<T> for MappedBloomFilter<T> {
+    fn insert(&self, _entry: &T) -> Result<(), BloomFilterError> {
+        Err(BloomFilterError::Unsupported(String::from(
+            "Mapped filter is read-only",
+        )))
+    }
+
+    fn contains(&self, entry: &T) -> bool {
+        MappedBloomFilter::contains(self, entry)
+    }
+
+    fn insert_all(&self, entries: &[&T]) -> Result<(), BloomFilterError> {
+        match entries.first() {
+            Some(entry) => ApproximateMembership::insert(self, entry),
+            None => Ok(()),
+        }
+    }
+
+    fn len(&self) -> Option<usize> {
+        Some(self.get_unique_entry_count())
+    }
+
+    fn false_positive_rate(&self) -> Option<SupportedFloatingPointType> {
+        Some(self.expected_false_positive_rate())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{collections::HashSet, sync::Mutex};
+
+    use super::ApproximateMembership;
+    use crate::bloom_filters::{
+        AgingBloomFilter, BloomFilter, BloomFilterError, CountingBloomFilter, CuckooFilter,
+        ScalableBloomFilter, StableBloomFilter, XorFilter,
+    };
+
+    #[derive(Default)]
+    struct ExactMembership(Mutex<HashSet<String>>);
+
+    impl ApproximateMembership for ExactMembership {
+        fn insert(&self, entry: &str) -> Result<(), BloomFilterError> {
+            self.0.lock().unwrap().insert(entry.to_string());
+            Ok(())
+        }
+
+        fn contains(&self, entry: &str) -> bool {
+            self.0.lock().unwrap().contains(entry)
+        }
+    }
+
+    fn build_filters() -> Vec<Box<dyn ApproximateMembership>> {
+        vec![
+            Box::new(BloomFilter::builder().with_max_size(1_000).build().unwrap()),
+            Box::new(
+                CountingBloomFilter::builder()
+                    .with_max_size(1_000)
+                    .build()
+                    .unwrap(),
+            ),
+            Box::new(
+                ScalableBloomFilter::builder()
+                    .with_max_size(1_000)
+                    .build()
+                    .unwrap(),
+            ),
+            Box::new(
+                StableBloomFilter::builder()
+                    .with_max_size(1_000)
+                    .build()
+                    .unwrap(),
+            ),
+            Box::new(
+                AgingBloomFilter::builder()
+                    .with_max_size(1_000)
+                    .build()
+                    .unwrap(),
+            ),
+            Box::new(
+                CuckooFilter::builder()
+                    .with_max_size(1_000)
+                    .build()
+                    .unwrap(),
+            ),
+            Box::new(ExactMembership::default()),
+        ]
+    }
+
+    #[test]
+    fn should_contain_inserted_entries_through_any_filter() {
+        for filter in build_filters() {
+            filter.insert_all(&["hello", "world"]).unwrap();
+            filter.insert("bloom").unwrap();
+
+            assert!(filter.contains("hello"));
+            assert!(filter.contains("world"));
+            assert!(filter.contains("bloom"));
+            assert!(!filter.contains("filter"));
+        }
+    }
+
+    #[test]
+    fn should_remove_entries_only_when_supported() {
+        for filter in build_filters() {
+            filter.insert("hello").unwrap();
+
+            match filter.remove("hello") {
+                Ok(removed) => {
+                    assert!(removed);
+                    assert!(!filter.contains("hello"));
+                }
+                Err(err) => {
+                    assert!(matches!(err, BloomFilterError::Unsupported(_)));
+                    assert!(filter.contains("hello"));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn should_report_optional_statistics() {
+        let bloom: BloomFilter = BloomFilter::builder().with_max_size(1_000).build().unwrap();
+        let cuckoo: CuckooFilter = CuckooFilter::builder()
+            .with_max_size(1_000)
+            .build()
+            .unwrap();
+        let exact = ExactMembership::default();
+
+        for filter in [&bloom as &dyn ApproximateMembership, &cuckoo, &exact] {
+            filter.insert("hello").unwrap();
+        }
+
+        assert_eq!(ApproximateMembership::len(&bloom), Some(1));
+        assert_eq!(ApproximateMembership::len(&cuckoo), Some(1));
+        assert_eq!(exact.len(), None);
+        assert_eq!(ApproximateMembership::is_empty(&cuckoo), Some(false));
+
+        assert!(bloom.false_positive_rate().unwrap() < 1e-6);
+        assert!(cuckoo.false_positive_rate().unwrap() < 0.01);
+        assert_eq!(exact.false_positive_rate(), None);
+    }
+
+    #[test]
+    fn should_reject_inserts_into_a_static_filter() {
+        let xor: XorFilter = XorFilter::builder().build_from(["hello"]).unwrap();
+        let filter: &dyn ApproximateMembership = &xor;
+
+        assert!(filter.contains("hello"));
+        assert!(matches!(
+            filter.insert("world"),
+            Err(BloomFilterError::Unsupported(_))
+        ));
+        assert!(filter.insert_all(&[]).is_ok());
+        assert!(filter.insert_all(&["world"]).is_err());
+        assert_eq!(filter.len(), Some(1));
+        assert_eq!(filter.false_positive_rate(), Some(1.0 / 256.0));
+    }
+}
@@ -1,52 +1,424 @@
+use std::{
+    fmt,
+    fs::File,
+    hash::Hash,
+    io::{BufReader, BufWriter, Read, Write},
+    marker::PhantomData,
+};
+
+pub use aging::{AgingBloomFilter, Clock, Rotation, SystemClock};
 use builder::Builder;
-use configurations::{ConfigError, Configurable, Configuration};
-use hashing::{strategy::Hashing, HashManager};
+use configurations::{ConfigError, Configurable, Configuration, Limits};
+pub use counting::CountingBloomFilter;
+pub use cuckoo::CuckooFilter;
+pub use hashing::strategy::{
+    DefaultHashingStrategy, Hashing, Murmur3HashingStrategy, SeaHashingStrategy,
+    SipHash13HashingStrategy, Xxh3HashingStrategy, CUSTOM_STRATEGY_IDENTIFIER,
+};
+pub use hashing::Layout;
+use hashing::{strategy, HashManager, Positions};
+pub use inspection::Inspection;
+pub use mapped::MappedBloomFilter;
+pub use membership::ApproximateMembership;
+pub use parameters::Parameters;
+use persistence::{Compressed, Header, XorHeader};
+pub use scalable::ScalableBloomFilter;
+pub use stable::StableBloomFilter;
+use statistics::{
+    estimate_cardinality_based_on_set_bits, estimate_false_positive_rate_based_on_fill_ratio,
+};
 use storage::Storage;
+pub use xor::XorFilter;
 
+mod aging;
 mod builder;
 mod configurations;
+mod counting;
+mod cuckoo;
 mod hashing;
+mod inspection;
+mod mapped;
+mod membership;
+mod parameters;
+mod persistence;
+mod scalable;
+#[cfg(feature = "serde")]
+mod serialization;
+mod stable;
+mod statistics;
 mod storage;
+mod xor;
 
 type SmallestIntType = u8;
-type SupportedFloatingPointType = f32;
+type SupportedFloatingPointType = f64;
 
 #[derive(Debug)]
 pub enum BloomFilterError {
     Configuration(ConfigError),
     Hashing(String),
     Storage(String),
+    Persistence(String),
+    Incompatible(String),
+    Full(String),
+    Unsupported(String),
 }
 
-pub struct BloomFilter {
+pub struct BloomFilter<T: ?Sized = str> {
     configuration: Configuration,
     manager: HashManager,
     storage: Storage,
+    entry: PhantomData<fn(&T)>,
 }
 
-impl BloomFilter {
-    pub fn builder() -> Builder {
+impl<T: Hash + ?Sized> BloomFilter<T> {
+    const BATCH_SIZE: usize = 16;
+
+    pub fn builder() -> Builder<Self> {
         Builder::default()
     }
 
     fn try_from(
-        max_size: usize,
-        max_tolerance: f32,
+        configuration: Configuration,
         strategy: Option<Box<dyn Hashing>>,
+        layout: Layout,
     ) -> Result<Self, BloomFilterError> {
-        let configuration = Configuration::try_from(max_tolerance, max_size)
-            .map_err(BloomFilterError::Configuration)?;
+        let manager = manage_hashing(&configuration, strategy, layout)?;
 
-        let mut manager_builder = HashManager::builder()
-            .with_tolerance(configuration.get_max_tolerance())
-            .with_total_bits(configuration.get_total_bits());
+        let storage = Storage::try_from(configuration.get_total_bits())
+            .map_err(String::from)
+            .map_err(BloomFilterError::Storage)?;
 
-        if let Some(strategy) = strategy {
-            manager_builder = manager_builder.use_strategy(strategy);
+        Ok(Self {
+            configuration,
+            manager,
+            storage,
+            entry: PhantomData,
+        })
+    }
+
+    pub fn load(path: &str) -> Result<Self, BloomFilterError> {
+        let file = File::open(path)
+            .map_err(|_| BloomFilterError::Persistence(format!("Cannot open file at {}", path)))?;
+
+        Self::read_from(&mut BufReader::new(file))
+    }
+
+    pub fn read_from<R: Read>(reader: &mut R) -> Result<Self, BloomFilterError> {
+        let header = Header::read_from(reader)
+            .map_err(String::from)
+            .map_err(BloomFilterError::Persistence)?;
+
+        let (configuration, manager) = restore_from(&header, &Limits::unbounded())?;
+
+        // The buffer only grows with the bytes the stream actually holds, so a forged header cannot reserve memory
+        let size_in_bytes = configuration
+            .get_total_bits()
+            .div_ceil(SmallestIntType::BITS as usize);
+        let mut bytes = vec![];
+        reader
+            .take(size_in_bytes as u64)
+            .read_to_end(&mut bytes)
+            .map_err(|_| BloomFilterError::Persistence(String::from("Cannot read the storage")))?;
+
+        if bytes.len() != size_in_bytes {
+            return Err(BloomFilterError::Persistence(String::from(
+                "Storage is truncated",
+            )));
         }
 
-        let manager = manager_builder
-            .build()
+        let storage = Storage::try_from_bytes(configuration.get_total_bits(), bytes)
+            .map_err(String::from)
+            .map_err(BloomFilterError::Storage)?;
+
+        let filter = Self {
+            configuration,
+            manager,
+            storage,
+            entry: PhantomData,
+        };
+
+        Ok(filter)
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), BloomFilterError> {
+        let file = File::create(path).map_err(|_| {
+            BloomFilterError::Persistence(format!("Cannot create file at {}", path))
+        })?;
+
+        let mut writer = BufWriter::new(file);
+
+        self.write_to(&mut writer)?;
+
+        writer
+            .flush()
+            .map_err(|_| BloomFilterError::Persistence(format!("Cannot write to file at {}", path)))
+    }
+
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> Result<(), BloomFilterError> {
+        self.header()?
+            .write_to(writer)
+            .map_err(String::from)
+            .map_err(BloomFilterError::Persistence)?;
+
+        writer
+            .write_all(&self.storage.as_bytes())
+            .map_err(|_| BloomFilterError::Persistence(String::from("Cannot write the storage")))
+    }
+
+    pub fn load_compressed(path: &str) -> Result<Self, BloomFilterError> {
+        let file = File::open(path)
+            .map_err(|_| BloomFilterError::Persistence(format!("Cannot open file at {}", path)))?;
+
+        Self::read_compressed_from(&mut BufReader::new(file))
+    }
+
+    pub fn read_compressed_from<R: Read>(reader: &mut R) -> Result<Self, BloomFilterError> {
+        let compressed = Compressed::read_header_from(reader)
+            .map_err(String::from)
+            .map_err(BloomFilterError::Persistence)?;
+
+        // Nothing but the header backs the size of the storage, so only the storage stays within the default limits
+        let (configuration, manager) = restore_from(
+            &compressed.header,
+            &Limits::default().with_max_capacity(usize::MAX),
+        )?;
+
+        let positions = compressed
+            .read_positions_from(reader)
+            .map_err(String::from)
+            .map_err(BloomFilterError::Persistence)?;
+
+        let storage = Storage::try_from(configuration.get_total_bits())
+            .map_err(String::from)
+            .map_err(BloomFilterError::Storage)?;
+
+        positions.into_iter().for_each(|idx| {
+            storage.write_bit_at(idx);
+        });
+
+        Ok(Self {
+            configuration,
+            manager,
+            storage,
+            entry: PhantomData,
+        })
+    }
+
+    pub fn save_compressed(&self, path: &str) -> Result<(), BloomFilterError> {
+        let file = File::create(path).map_err(|_| {
+            BloomFilterError::Persistence(format!("Cannot create file at {}", path))
+        })?;
+
+        let mut writer = BufWriter::new(file);
+
+        self.write_compressed_to(&mut writer)?;
+
+        writer
+            .flush()
+            .map_err(|_| BloomFilterError::Persistence(format!("Cannot write to file at {}", path)))
+    }
+
+    // Only the positions of set bits are written, so sparse filters shrink far below their storage size
+    pub fn write_compressed_to<W: Write>(&self, writer: &mut W) -> Result<(), BloomFilterError> {
+        // Other threads may insert meanwhile, one pass keeps the count and the encoded positions in step
+        let positions: Vec<usize> = self.storage.iter_set_bits().collect();
+
+        Compressed::write_to(&self.header()?, &positions, writer)
+            .map_err(String::from)
+            .map_err(BloomFilterError::Persistence)
+    }
+
+    fn header(&self) -> Result<Header, BloomFilterError> {
+        self.manager
+            .try_restore_strategy()
+            .map_err(String::from)
+            .map_err(BloomFilterError::Persistence)?;
+
+        Ok(Header {
+            total_bits: self.configuration.get_total_bits() as u64,
+            num_of_hash_funcs: self.manager.get_num_of_hash_funcs() as u32,
+            seed: self.manager.get_seed(),
+            strategy_identifier: self.manager.get_strategy_identifier(),
+            layout_identifier: self.manager.get_layout().get_identifier(),
+            fold_factor: self.manager.get_fold_factor() as u64,
+            max_size: self.configuration.get_max_size() as u64,
+            max_tolerance: self.configuration.get_max_tolerance(),
+            unique_entry_count: self.configuration.get_unique_entry_count() as u64,
+        })
+    }
+
+    pub fn estimate_unique_entry_count(&self) -> SupportedFloatingPointType {
+        estimate_cardinality_based_on_set_bits(
+            self.configuration.get_total_bits(),
+            self.manager.get_num_of_hash_funcs(),
+            self.storage.count_set_bits(),
+        )
+    }
+
+    pub fn fill_ratio(&self) -> SupportedFloatingPointType {
+        self.storage.count_set_bits() as SupportedFloatingPointType
+            / self.configuration.get_total_bits() as SupportedFloatingPointType
+    }
+
+    pub fn estimate_false_positive_rate(&self) -> SupportedFloatingPointType {
+        estimate_false_positive_rate_based_on_fill_ratio(
+            self.fill_ratio(),
+            self.manager.get_num_of_hash_funcs(),
+        )
+    }
+
+    pub fn get_max_tolerance(&self) -> SupportedFloatingPointType {
+        self.configuration.get_max_tolerance()
+    }
+
+    pub fn get_total_bits(&self) -> usize {
+        self.configuration.get_total_bits()
+    }
+
+    pub fn get_num_of_hash_funcs(&self) -> usize {
+        self.manager.get_num_of_hash_funcs()
+    }
+
+    pub fn get_layout(&self) -> Layout {
+        self.manager.get_layout()
+    }
+
+    // Only filters hashed with a built-in strategy can be described by their parameters
+    pub fn get_parameters(&self) -> Result<Parameters, BloomFilterError> {
+        self.manager
+            .try_restore_strategy()
+            .map_err(String::from)
+            .map_err(BloomFilterError::Hashing)?;
+
+        Parameters::try_from(
+            self.configuration.get_max_size(),
+            self.configuration.get_max_tolerance(),
+            self.manager.get_strategy_identifier(),
+            self.manager.get_seed(),
+        )
+    }
+
+    pub fn expected_false_positive_rate(&self) -> SupportedFloatingPointType {
+        self.configuration.get_expected_false_positive_rate()
+    }
+
+    pub fn inspect(&self) -> Inspection {
+        let set_bits = self.storage.count_set_bits();
+
+        Inspection {
+            total_bits: self.configuration.get_total_bits(),
+            size_in_bytes: self
+                .configuration
+                .get_total_bits()
+                .div_ceil(SmallestIntType::BITS as usize),
+            num_of_hash_funcs: self.manager.get_num_of_hash_funcs(),
+            seed: self.manager.get_seed(),
+            strategy: self.manager.get_strategy_name(),
+            layout: self.manager.get_layout(),
+            fold_factor: self.manager.get_fold_factor(),
+            max_size: self.configuration.get_max_size(),
+            max_tolerance: self.configuration.get_max_tolerance(),
+            unique_entry_count: self.configuration.get_unique_entry_count(),
+            estimated_unique_entry_count: estimate_cardinality_based_on_set_bits(
+                self.configuration.get_total_bits(),
+                self.manager.get_num_of_hash_funcs(),
+                set_bits,
+            ),
+            set_bits,
+            estimated_false_positive_rate: estimate_false_positive_rate_based_on_fill_ratio(
+                set_bits as SupportedFloatingPointType
+                    / self.configuration.get_total_bits() as SupportedFloatingPointType,
+                self.manager.get_num_of_hash_funcs(),
+            ),
+            regions: self.storage.count_set_bits_per_region(
+                Inspection::NUM_OF_REGIONS,
+                self.configuration.get_total_bits(),
+            ),
+        }
+    }
+
+    pub fn is_within_tolerance(&self) -> bool {
+        self.estimate_false_positive_rate() <= self.get_max_tolerance()
+    }
+
+    pub fn union(&self, other: &BloomFilter<T>) -> Result<BloomFilter<T>, BloomFilterError> {
+        let filter = self.try_clone_empty()?;
+        filter.union_with(self)?;
+        filter.union_with(other)?;
+        Ok(filter)
+    }
+
+    pub fn intersect(&self, other: &BloomFilter<T>) -> Result<BloomFilter<T>, BloomFilterError> {
+        let filter = self.try_clone_empty()?;
+        filter.union_with(self)?;
+        filter.intersect_with(other)?;
+        Ok(filter)
+    }
+
+    // The entry count of a union is only an upper bound, the true count lies between the larger one and the sum
+    pub fn union_with(&self, other: &BloomFilter<T>) -> Result<(), BloomFilterError> {
+        self.ensure_compatible_with(other)?;
+
+        self.storage.union_with(&other.storage);
+
+        self.configuration.restore_unique_entry_count(
+            self.configuration.get_unique_entry_count()
+                + other.configuration.get_unique_entry_count(),
+        );
+
+        Ok(())
+    }
+
+    // The entry count of an intersection is only an upper bound, bits set by different entries may overlap
+    pub fn intersect_with(&self, other: &BloomFilter<T>) -> Result<(), BloomFilterError> {
+        self.ensure_compatible_with(other)?;
+
+        self.storage.intersect_with(&other.storage);
+
+        self.configuration.restore_unique_entry_count(
+            self.configuration
+                .get_unique_entry_count()
+                .min(other.configuration.get_unique_entry_count()),
+        );
+
+        Ok(())
+    }
+
+    fn ensure_compatible_with(&self, other: &BloomFilter<T>) -> Result<(), BloomFilterError> {
+        let mismatch = if self.configuration.get_total_bits()
+            != other.configuration.get_total_bits()
+        {
+            "total bits"
+        } else if self.manager.get_num_of_hash_funcs() != other.manager.get_num_of_hash_funcs() {
+            "number of hash functions"
+        } else if self.manager.get_seed() != other.manager.get_seed() {
+            "seed"
+        } else if self.manager.get_strategy_identifier() != other.manager.get_strategy_identifier()
+        {
+            "hashing strategy"
+        } else if self.manager.get_layout() != other.manager.get_layout() {
+            "layout"
+        } else if self.manager.get_fold_factor() != other.manager.get_fold_factor() {
+            "fold factor"
+        } else {
+            return Ok(());
+        };
+
+        Err(BloomFilterError::Incompatible(format!(
+            "Filters have different {}",
+            mismatch
+        )))
+    }
+
+    fn try_clone_empty(&self) -> Result<BloomFilter<T>, BloomFilterError> {
+        let configuration = self
+            .configuration
+            .try_clone_empty()
+            .map_err(BloomFilterError::Configuration)?;
+
+        let manager = self
+            .manager
+            .try_clone()
             .map_err(String::from)
             .map_err(BloomFilterError::Hashing)?;
 
@@ -58,28 +430,940 @@ impl BloomFilter {
             configuration,
             manager,
             storage,
+            entry: PhantomData,
         })
     }
 
-    pub fn insert(&self, entry: String) {
-        let positions = self.manager.hash(&entry);
+    // Every bit lands on its position modulo the folded size, which is where the folded manager probes it
+    pub fn fold(&self, factor: usize) -> Result<BloomFilter<T>, BloomFilterError> {
+        let configuration = self
+            .configuration
+            .try_fold(factor)
+            .map_err(BloomFilterError::Configuration)?;
 
-        let is_new_entry = positions
-            .into_iter()
-            .map(|idx| self.storage.write_bit_at(idx))
-            .all(|x| x);
+        let manager = self
+            .manager
+            .try_fold(factor)
+            .map_err(String::from)
+            .map_err(BloomFilterError::Hashing)?;
+
+        let storage = Storage::try_from(configuration.get_total_bits())
+            .map_err(String::from)
+            .map_err(BloomFilterError::Storage)?;
+
+        self.storage.iter_set_bits().for_each(|idx| {
+            storage.write_bit_at(idx % configuration.get_total_bits());
+        });
+
+        Ok(Self {
+            configuration,
+            manager,
+            storage,
+            entry: PhantomData,
+        })
+    }
+
+    pub fn clear(&self) {
+        self.storage.clear();
+        self.configuration.restore_unique_entry_count(0);
+    }
+
+    pub fn insert(&self, entry: &T) {
+        self.insert_positions(self.manager.hash(entry));
+    }
+
+    pub fn insert_many<'a, I>(&self, entries: I)
+    where
+        I: IntoIterator<Item = &'a T>,
+        T: 'a,
+    {
+        let mut entries = entries.into_iter();
+        let mut batch = Vec::with_capacity(Self::BATCH_SIZE);
+
+        loop {
+            self.hash_and_prefetch_batch(&mut entries, &mut batch);
+
+            if batch.is_empty() {
+                break;
+            }
+
+            batch
+                .drain(..)
+                .for_each(|positions| self.insert_positions(positions));
+        }
+    }
+
+    pub fn contains(&self, entry: &T) -> bool {
+        self.contains_positions(self.manager.hash(entry))
+    }
+
+    pub fn contains_many<'a, I>(&self, entries: I) -> Vec<bool>
+    where
+        I: IntoIterator<Item = &'a T>,
+        T: 'a,
+    {
+        let mut entries = entries.into_iter();
+        let mut results = Vec::with_capacity(entries.size_hint().0);
+        let mut batch = Vec::with_capacity(Self::BATCH_SIZE);
+
+        loop {
+            self.hash_and_prefetch_batch(&mut entries, &mut batch);
+
+            if batch.is_empty() {
+                break;
+            }
 
-        if is_new_entry {
+            results.extend(
+                batch
+                    .drain(..)
+                    .map(|positions| self.contains_positions(positions)),
+            );
+        }
+
+        results
+    }
+
+    // Hashing a whole batch up front lets the memory of every probe load while the batch is processed,
+    // the batch is reused so no entry allocates
+    fn hash_and_prefetch_batch<'a, I>(&self, entries: &mut I, batch: &mut Vec<Positions>)
+    where
+        I: Iterator<Item = &'a T>,
+        T: 'a,
+    {
+        batch.extend(
+            entries
+                .take(Self::BATCH_SIZE)
+                .map(|entry| self.manager.hash(entry)),
+        );
+
+        batch
+            .iter()
+            .flat_map(Positions::clone)
+            .for_each(|idx| self.storage.prefetch_bit_at(idx));
+    }
+
+    fn insert_positions(&self, positions: Positions) {
+        let num_of_written_bits = positions
+            .filter(|&idx| self.storage.write_bit_at(idx))
+            .count();
+
+        // An entry that sets no new bit is either a duplicate or a false positive
+        if num_of_written_bits > 0 {
             self.configuration.increase_unique_entry_count();
         }
     }
 
-    pub fn contains(&self, entry: &str) -> bool {
-        let positions = self.manager.hash(&entry);
+    fn contains_positions(&self, mut positions: Positions) -> bool {
+        positions.all(|idx| self.storage.read_bit_at(idx) != 0)
+    }
+}
+
+impl<T: ?Sized> fmt::Debug for BloomFilter<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BloomFilter")
+            .field("configuration", &self.configuration)
+            .field("manager", &self.manager)
+            .finish_non_exhaustive()
+    }
+}
+
+fn manage_hashing(
+    configuration: &Configuration,
+    strategy: Option<Box<dyn Hashing>>,
+    layout: Layout,
+) -> Result<HashManager, BloomFilterError> {
+    let mut manager_builder = HashManager::builder()
+        .with_num_of_hash_funcs(configuration.get_num_of_hash_funcs())
+        .with_total_bits(configuration.get_total_bits())
+        .with_layout(layout);
+
+    if let Some(strategy) = strategy {
+        manager_builder = manager_builder.use_strategy(strategy);
+    }
+
+    manager_builder
+        .build()
+        .map_err(String::from)
+        .map_err(BloomFilterError::Hashing)
+}
+
+// Restores the configuration and hashing a persisted header describes, reading the storage is left to the caller
+fn restore_from(
+    header: &Header,
+    limits: &Limits,
+) -> Result<(Configuration, HashManager), BloomFilterError> {
+    let strategy =
+        strategy::from_identifier(header.strategy_identifier, header.seed).ok_or_else(|| {
+            BloomFilterError::Persistence(format!(
+                "Unknown hashing strategy {}",
+                header.strategy_identifier
+            ))
+        })?;
+
+    let layout = Layout::from_identifier(header.layout_identifier).ok_or_else(|| {
+        BloomFilterError::Persistence(format!("Unknown layout {}", header.layout_identifier))
+    })?;
+
+    // A header written on a 64-bit platform may not fit the usize of this one
+    let to_usize = |value: u64, field: &str| {
+        usize::try_from(value).map_err(|_| {
+            BloomFilterError::Persistence(format!("{} does not fit this platform", field))
+        })
+    };
+
+    let total_bits = to_usize(header.total_bits, "Total bits")?;
+    let fold_factor = to_usize(header.fold_factor, "Fold factor")?;
 
-        positions
+    let configuration = Configuration::try_restore(
+        header.max_tolerance as SupportedFloatingPointType,
+        to_usize(header.max_size, "Max size")?,
+        total_bits,
+        to_usize(header.num_of_hash_funcs as u64, "Number of hash functions")?,
+        limits,
+    )
+    .map_err(BloomFilterError::Configuration)?;
+
+    configuration
+        .restore_unique_entry_count(to_usize(header.unique_entry_count, "Unique entry count")?);
+
+    // A folded filter still hashes over the bits it had before folding
+    let hashed_bits = total_bits
+        .checked_mul(fold_factor)
+        .ok_or_else(|| BloomFilterError::Persistence(String::from("Fold factor is too large")))?;
+
+    let manager = HashManager::builder()
+        .with_num_of_hash_funcs(configuration.get_num_of_hash_funcs())
+        .with_total_bits(hashed_bits)
+        .with_layout(layout)
+        .with_fold_factor(fold_factor)
+        .use_strategy(strategy)
+        .build()
+        .map_err(String::from)
+        .map_err(BloomFilterError::Hashing)?;
+
+    Ok((configuration, manager))
+}
+
+// load picks the filter type from the magic bytes so snapshots of any persisted filter can be opened
+pub fn load(path: &str) -> Result<Box<dyn ApproximateMembership>, BloomFilterError> {
+    let file = File::open(path)
+        .map_err(|_| BloomFilterError::Persistence(format!("Cannot open file at {}", path)))?;
+
+    let mut reader = BufReader::new(file);
+
+    let mut magic_bytes = [0; 4];
+    reader
+        .read_exact(&mut magic_bytes)
+        .map_err(|_| BloomFilterError::Persistence(String::from("Header is truncated")))?;
+
+    let mut reader = (&magic_bytes[..]).chain(reader);
+
+    match magic_bytes {
+        Header::MAGIC_BYTES => Ok(Box::new(BloomFilter::<str>::read_from(&mut reader)?)),
+        XorHeader::MAGIC_BYTES => Ok(Box::new(XorFilter::<str>::read_from(&mut reader)?)),
+        Compressed::MAGIC_BYTES => Ok(Box::new(BloomFilter::<str>::read_compressed_from(
+            &mut reader,
+        )?)),
+        _ => Err(BloomFilterError::Persistence(String::from(
+            "Unknown magic bytes",
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        net::{IpAddr, Ipv4Addr},
+        sync::Arc,
+    };
+
+    use super::{
+        configurations::Configurable, BloomFilter, BloomFilterError, DefaultHashingStrategy,
+        Hashing, Layout, Murmur3HashingStrategy, SeaHashingStrategy, SipHash13HashingStrategy,
+        XorFilter, Xxh3HashingStrategy,
+    };
+
+    #[test]
+    fn should_be_shareable_across_threads() {
+        fn assert_send_and_sync<T: Send + Sync>() {}
+
+        assert_send_and_sync::<BloomFilter>();
+        assert_send_and_sync::<BloomFilter<[u8]>>();
+        assert_send_and_sync::<super::CountingBloomFilter>();
+        assert_send_and_sync::<super::ScalableBloomFilter>();
+        assert_send_and_sync::<super::StableBloomFilter>();
+        assert_send_and_sync::<super::AgingBloomFilter>();
+        assert_send_and_sync::<super::CuckooFilter>();
+        assert_send_and_sync::<super::XorFilter>();
+        assert_send_and_sync::<super::MappedBloomFilter>();
+        assert_send_and_sync::<crate::spell_checker::SpellChecker>();
+        assert_send_and_sync::<crate::weak_password_detector::PasswordDetector>();
+    }
+
+    #[test]
+    fn should_contain_entries_of_any_hashable_type() {
+        let numbers = BloomFilter::<u64>::builder()
+            .with_max_size(1_000)
+            .build()
+            .unwrap();
+        (0..100).for_each(|number| numbers.insert(&number));
+        assert!((0..100).all(|number| numbers.contains(&number)));
+        assert!(!numbers.contains(&1_000));
+
+        let addresses = BloomFilter::<IpAddr>::builder()
+            .with_max_size(1_000)
+            .build()
+            .unwrap();
+        addresses.insert(&IpAddr::V4(Ipv4Addr::LOCALHOST));
+        assert!(addresses.contains(&IpAddr::V4(Ipv4Addr::LOCALHOST)));
+        assert!(!addresses.contains(&IpAddr::V4(Ipv4Addr::BROADCAST)));
+
+        let blobs = BloomFilter::<[u8]>::builder()
+            .with_max_size(1_000)
+            .build()
+            .unwrap();
+        blobs.insert(&[0xde, 0xad, 0xbe, 0xef]);
+        assert!(blobs.contains(&[0xde, 0xad, 0xbe, 0xef]));
+        assert!(!blobs.contains(&[0xde, 0xad]));
+    }
+
+    #[test]
+    fn should_insert_and_query_entries_in_batches() {
+        let filter: BloomFilter = BloomFilter::builder()
+            .with_max_size(10_000)
+            .build()
+            .unwrap();
+        let words: Vec<String> = (0..1_000).map(|idx| format!("word-{}", idx)).collect();
+
+        filter.insert_many(words.iter().map(String::as_str));
+
+        assert!(words.iter().all(|word| filter.contains(word)));
+        assert!(filter
+            .contains_many(words.iter().map(String::as_str))
             .into_iter()
-            .map(|idx| self.storage.read_bit_at(idx))
-            .all(|bit| bit != 0)
+            .all(|found| found));
+        assert_eq!(
+            filter.contains_many(["word-1", "missing", "word-999"]),
+            vec![true, false, true]
+        );
+        assert!(filter.contains_many([]).is_empty());
+    }
+
+    #[test]
+    fn should_contain_entries_inserted_from_many_threads() {
+        let filter = Arc::new(
+            BloomFilter::builder()
+                .with_max_size(10_000)
+                .build()
+                .unwrap(),
+        );
+
+        let handles: Vec<_> = (0..8)
+            .map(|thread| {
+                let filter = Arc::clone(&filter);
+                std::thread::spawn(move || {
+                    (0..1_000).for_each(|idx| filter.insert(&format!("word-{}-{}", thread, idx)))
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .for_each(|handle| handle.join().unwrap());
+
+        assert!((0..8).all(|thread| {
+            (0..1_000).all(|idx| filter.contains(&format!("word-{}-{}", thread, idx)))
+        }));
+    }
+
+    #[test]
+    fn should_estimate_number_of_inserted_entries() {
+        let filter: BloomFilter = BloomFilter::builder()
+            .with_max_size(10_000)
+            .build()
+            .unwrap();
+        (0..5_000).for_each(|idx| filter.insert(&format!("word-{}", idx)));
+
+        let estimate = filter.estimate_unique_entry_count();
+
+        assert!((4_900.0..5_100.0).contains(&estimate));
+        assert!((4_900..=5_000).contains(&filter.configuration.get_unique_entry_count()));
+    }
+
+    #[test]
+    fn should_report_empty_filter_statistics() {
+        let filter: BloomFilter = BloomFilter::builder().with_max_size(1_000).build().unwrap();
+
+        assert_eq!(filter.estimate_unique_entry_count(), 0.0);
+        assert_eq!(filter.fill_ratio(), 0.0);
+        assert_eq!(filter.estimate_false_positive_rate(), 0.0);
+        assert!(filter.is_within_tolerance());
+    }
+
+    #[test]
+    fn should_detect_drift_past_tolerance_when_overfilled() {
+        let filter: BloomFilter = BloomFilter::builder()
+            .with_max_size(1_000)
+            .with_max_tolerance(0.01)
+            .build()
+            .unwrap();
+
+        (0..1_000).for_each(|idx| filter.insert(&format!("word-{}", idx)));
+        assert!(filter.is_within_tolerance());
+
+        (1_000..3_000).for_each(|idx| filter.insert(&format!("word-{}", idx)));
+        assert!(!filter.is_within_tolerance());
+        assert!(filter.fill_ratio() > 0.5);
+    }
+
+    #[test]
+    fn should_keep_working_with_every_built_in_strategy() {
+        let strategies: Vec<Box<dyn Hashing>> = vec![
+            Box::new(DefaultHashingStrategy::with_seed(7)),
+            Box::new(Xxh3HashingStrategy::with_seed(7)),
+            Box::new(Murmur3HashingStrategy::with_seed(7)),
+            Box::new(SipHash13HashingStrategy::with_seed(7)),
+            Box::new(SeaHashingStrategy::with_seed(7)),
+        ];
+
+        for strategy in strategies {
+            let identifier = strategy.get_identifier();
+            let filter: BloomFilter = BloomFilter::builder()
+                .with_max_size(1_000)
+                .with_strategy(strategy)
+                .build()
+                .unwrap();
+            (0..500).for_each(|idx| filter.insert(&format!("word-{}", idx)));
+
+            let mut bytes = vec![];
+            filter.write_to(&mut bytes).unwrap();
+            let restored = BloomFilter::<str>::read_from(&mut bytes.as_slice()).unwrap();
+
+            assert_eq!(restored.manager.get_strategy_identifier(), identifier);
+            assert_eq!(restored.manager.get_seed(), 7);
+            assert!((0..500).all(|idx| restored.contains(&format!("word-{}", idx))));
+            assert!(restored.estimate_false_positive_rate() < 0.01);
+        }
+    }
+
+    struct CustomHashingStrategy;
+
+    impl Hashing for CustomHashingStrategy {
+        fn hash(&self, entry: &[u8]) -> (u64, u64) {
+            (entry.len() as u64, 1)
+        }
+    }
+
+    struct ImpersonatingHashingStrategy;
+
+    impl Hashing for ImpersonatingHashingStrategy {
+        fn hash(&self, entry: &[u8]) -> (u64, u64) {
+            (entry.len() as u64, 1)
+        }
+
+        fn get_identifier(&self) -> u8 {
+            0
+        }
+    }
+
+    #[test]
+    fn should_return_err_when_persisting_filter_with_custom_strategy() {
+        let strategies: Vec<Box<dyn Hashing>> = vec![
+            Box::new(CustomHashingStrategy),
+            Box::new(ImpersonatingHashingStrategy),
+        ];
+
+        for strategy in strategies {
+            let filter: BloomFilter = BloomFilter::builder()
+                .with_max_size(1_000)
+                .with_strategy(strategy)
+                .build()
+                .unwrap();
+            filter.insert("hello");
+            assert!(filter.contains("hello"));
+
+            let mut bytes = vec![];
+            assert!(matches!(
+                filter.write_to(&mut bytes),
+                Err(BloomFilterError::Persistence(_))
+            ));
+            assert!(matches!(
+                filter.write_compressed_to(&mut bytes),
+                Err(BloomFilterError::Persistence(_))
+            ));
+            assert!(bytes.is_empty());
+        }
+    }
+
+    #[test]
+    fn should_write_every_bit_of_entry_whose_first_bit_is_already_set() {
+        let filter: BloomFilter = BloomFilter::builder().with_max_size(1_000).build().unwrap();
+        let first_position = filter.manager.hash("hello").next().unwrap();
+        filter.storage.write_bit_at(first_position);
+
+        filter.insert("hello");
+
+        assert!(filter.contains("hello"));
+    }
+
+    #[test]
+    fn should_contain_entries_of_either_filter_after_union() {
+        let (left, right) = (
+            BloomFilter::builder().with_max_size(1_000).build().unwrap(),
+            BloomFilter::builder().with_max_size(1_000).build().unwrap(),
+        );
+        left.insert("hello");
+        right.insert("world");
+
+        let union = left.union(&right).unwrap();
+        assert!(union.contains("hello"));
+        assert!(union.contains("world"));
+        assert!(!left.contains("world"));
+
+        left.union_with(&right).unwrap();
+        assert!(left.contains("world"));
+    }
+
+    #[test]
+    fn should_contain_only_shared_entries_after_intersection() {
+        let (left, right) = (
+            BloomFilter::builder().with_max_size(1_000).build().unwrap(),
+            BloomFilter::builder().with_max_size(1_000).build().unwrap(),
+        );
+        left.insert("hello");
+        left.insert("bloom");
+        right.insert("bloom");
+        right.insert("world");
+
+        let intersection = left.intersect(&right).unwrap();
+        assert!(intersection.contains("bloom"));
+        assert!(!intersection.contains("hello"));
+        assert!(!intersection.contains("world"));
+
+        left.intersect_with(&right).unwrap();
+        assert!(left.contains("bloom"));
+        assert!(!left.contains("hello"));
+    }
+
+    #[test]
+    fn should_return_err_when_combining_incompatible_filters() {
+        let filter: BloomFilter = BloomFilter::builder().with_max_size(1_000).build().unwrap();
+        let incompatible_filters = vec![
+            BloomFilter::builder().with_max_size(2_000).build().unwrap(),
+            BloomFilter::builder()
+                .with_max_size(1_000)
+                .with_max_tolerance(0.1)
+                .build()
+                .unwrap(),
+            BloomFilter::builder()
+                .with_max_size(1_000)
+                .with_layout(Layout::Blocked)
+                .build()
+                .unwrap(),
+        ];
+
+        for other in incompatible_filters {
+            assert!(matches!(
+                filter.union(&other),
+                Err(BloomFilterError::Incompatible(_))
+            ));
+            assert!(matches!(
+                filter.intersect_with(&other),
+                Err(BloomFilterError::Incompatible(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn should_build_from_explicit_bits_and_hash_functions() {
+        let filter: BloomFilter = BloomFilter::builder()
+            .with_max_size(1_000)
+            .with_total_bits(8_000)
+            .with_num_of_hash_funcs(3)
+            .build()
+            .unwrap();
+
+        assert_eq!(filter.get_total_bits(), 8_000);
+        assert_eq!(filter.get_num_of_hash_funcs(), 3);
+        assert!((filter.expected_false_positive_rate() - 0.0306).abs() < 0.0005);
+    }
+
+    #[test]
+    fn should_pick_optimal_hash_functions_for_bits_per_entry_and_memory_budget() {
+        let by_bits_per_entry: BloomFilter = BloomFilter::builder()
+            .with_max_size(1_000)
+            .with_bits_per_entry(10.0)
+            .build()
+            .unwrap();
+
+        assert_eq!(by_bits_per_entry.get_total_bits(), 10_000);
+        assert_eq!(by_bits_per_entry.get_num_of_hash_funcs(), 7);
+        assert!(by_bits_per_entry.expected_false_positive_rate() < 0.01);
+
+        let by_memory_budget: BloomFilter = BloomFilter::builder()
+            .with_max_size(1_000)
+            .with_memory_budget(1_024)
+            .build()
+            .unwrap();
+
+        assert_eq!(by_memory_budget.get_total_bits(), 8_192);
+        assert_eq!(by_memory_budget.get_num_of_hash_funcs(), 6);
+        assert_eq!(
+            by_memory_budget.get_max_tolerance(),
+            by_memory_budget.expected_false_positive_rate()
+        );
+    }
+
+    #[test]
+    fn should_return_err_when_scalable_filter_is_not_sized_by_tolerance() {
+        let maybe_filter = super::ScalableBloomFilter::<str>::builder()
+            .with_memory_budget(1_024)
+            .build();
+
+        assert!(matches!(
+            maybe_filter,
+            Err(BloomFilterError::Configuration(_))
+        ));
+    }
+
+    #[test]
+    fn should_apply_capacity_and_total_bits_limits_of_builder() {
+        let maybe_filter: Result<BloomFilter, _> =
+            BloomFilter::builder().with_max_size(20_000_000).build();
+        assert!(maybe_filter.is_err());
+
+        let filter: BloomFilter = BloomFilter::builder()
+            .with_max_size(20_000_000)
+            .with_capacity_limit(20_000_000)
+            .build()
+            .unwrap();
+        assert_eq!(filter.configuration.get_max_size(), 20_000_000);
+
+        let maybe_filter: Result<BloomFilter, _> = BloomFilter::builder()
+            .with_max_size(1_000)
+            .with_total_bits_limit(1_000)
+            .build();
+        assert!(maybe_filter.is_err());
+    }
+
+    #[test]
+    fn should_keep_explicit_sizing_after_round_trip() {
+        let filter: BloomFilter = BloomFilter::builder()
+            .with_max_size(1_000)
+            .with_total_bits(8_000)
+            .with_num_of_hash_funcs(3)
+            .build()
+            .unwrap();
+        filter.insert("hello");
+
+        let mut bytes = vec![];
+        filter.write_to(&mut bytes).unwrap();
+        let restored = BloomFilter::<str>::read_from(&mut bytes.as_slice()).unwrap();
+
+        assert_eq!(restored.get_total_bits(), 8_000);
+        assert_eq!(restored.get_num_of_hash_funcs(), 3);
+        assert!(restored.contains("hello"));
+    }
+
+    #[test]
+    fn should_contain_entries_after_round_trip() {
+        let filter: BloomFilter = BloomFilter::builder().with_max_size(1_000).build().unwrap();
+        filter.insert("hello");
+        filter.insert("world");
+
+        let mut bytes = vec![];
+        filter.write_to(&mut bytes).unwrap();
+        let restored = BloomFilter::read_from(&mut bytes.as_slice()).unwrap();
+
+        assert!(restored.contains("hello"));
+        assert!(restored.contains("world"));
+        assert!(!restored.contains("bloom"));
+        assert_eq!(restored.storage.as_bytes(), filter.storage.as_bytes());
+    }
+
+    #[test]
+    fn should_return_err_when_storage_is_truncated() {
+        let filter: BloomFilter = BloomFilter::builder().with_max_size(1_000).build().unwrap();
+
+        let mut bytes = vec![];
+        filter.write_to(&mut bytes).unwrap();
+        bytes.pop();
+
+        assert!(BloomFilter::<str>::read_from(&mut bytes.as_slice()).is_err());
+    }
+
+    #[test]
+    fn should_return_err_without_reserving_storage_a_forged_header_claims() {
+        let filter: BloomFilter = BloomFilter::builder().with_max_size(1_000).build().unwrap();
+
+        let mut bytes = vec![];
+        filter.write_to(&mut bytes).unwrap();
+        bytes.truncate(super::Header::SIZE);
+        bytes[6..14].copy_from_slice(&(1u64 << 45).to_le_bytes());
+
+        assert!(matches!(
+            BloomFilter::<str>::read_from(&mut bytes.as_slice()),
+            Err(BloomFilterError::Persistence(_))
+        ));
+    }
+
+    #[test]
+    fn should_return_err_when_forged_header_claims_too_many_hash_functions() {
+        let filter: BloomFilter = BloomFilter::builder().with_max_size(1_000).build().unwrap();
+
+        let mut bytes = vec![];
+        filter.write_to(&mut bytes).unwrap();
+        bytes[14..18].copy_from_slice(&u32::MAX.to_le_bytes());
+
+        assert!(matches!(
+            BloomFilter::<str>::read_from(&mut bytes.as_slice()),
+            Err(BloomFilterError::Configuration(_))
+        ));
+    }
+
+    #[test]
+    fn should_save_and_load_from_file() {
+        let path = std::env::temp_dir().join("bloom_filter_should_save_and_load_from_file.bin");
+        let path = path.to_str().unwrap();
+
+        let filter: BloomFilter = BloomFilter::builder().with_max_size(1_000).build().unwrap();
+        filter.insert("hello");
+        filter.save(path).unwrap();
+
+        let restored = BloomFilter::load(path).unwrap();
+        std::fs::remove_file(path).unwrap();
+
+        assert!(restored.contains("hello"));
+    }
+
+    #[test]
+    fn should_load_any_persisted_filter_from_its_magic_bytes() {
+        let path = std::env::temp_dir().join("bloom_filter_should_load_any_persisted_filter.bin");
+        let path = path.to_str().unwrap();
+
+        let bloom: BloomFilter = BloomFilter::builder().with_max_size(1_000).build().unwrap();
+        bloom.insert("hello");
+        bloom.save(path).unwrap();
+
+        let restored = super::load(path).unwrap();
+        assert!(restored.contains("hello"));
+        assert!(restored.insert("world").is_ok());
+
+        let xor: XorFilter = XorFilter::builder().build_from(["hello"]).unwrap();
+        xor.save(path).unwrap();
+
+        let restored = super::load(path).unwrap();
+        assert!(restored.contains("hello"));
+        assert!(restored.insert("world").is_err());
+
+        std::fs::write(path, b"NOPE").unwrap();
+        assert!(super::load(path).is_err());
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn should_keep_false_positive_rate_close_to_tolerance_in_every_layout() {
+        for layout in [Layout::Flat, Layout::Partitioned, Layout::Blocked] {
+            let filter: BloomFilter<u64> = BloomFilter::builder()
+                .with_max_size(10_000)
+                .with_layout(layout)
+                .build()
+                .unwrap();
+
+            filter.insert_many(&(0..10_000).collect::<Vec<_>>());
+
+            assert_eq!(filter.get_layout(), layout);
+            assert!((0..10_000).all(|entry| filter.contains(&entry)));
+
+            let false_positives = (10_000..110_000)
+                .filter(|entry| filter.contains(entry))
+                .count();
+
+            // Blocks fill unevenly, which costs a blocked filter some accuracy
+            assert!(false_positives < 2_000);
+        }
+    }
+
+    #[test]
+    fn should_keep_layout_after_round_trip() {
+        let filter: BloomFilter = BloomFilter::builder()
+            .with_max_size(1_000)
+            .with_layout(Layout::Partitioned)
+            .build()
+            .unwrap();
+        filter.insert("hello");
+
+        let mut bytes = vec![];
+        filter.write_to(&mut bytes).unwrap();
+        let restored = BloomFilter::<str>::read_from(&mut bytes.as_slice()).unwrap();
+
+        assert_eq!(restored.get_layout(), Layout::Partitioned);
+        assert!(restored.contains("hello"));
+        assert!(restored.union(&filter).is_ok());
+    }
+
+    #[test]
+    fn should_return_err_when_partitions_are_empty() {
+        let maybe_filter: Result<BloomFilter, _> = BloomFilter::builder()
+            .with_max_size(1)
+            .with_total_bits(2)
+            .with_num_of_hash_funcs(3)
+            .with_layout(Layout::Partitioned)
+            .build();
+
+        assert!(matches!(maybe_filter, Err(BloomFilterError::Hashing(_))));
+    }
+
+    #[test]
+    fn should_answer_the_same_after_compressed_round_trip() {
+        let filter: BloomFilter<u64> = BloomFilter::builder()
+            .with_max_size(100_000)
+            .with_layout(Layout::Blocked)
+            .build()
+            .unwrap();
+        filter.insert_many(&(0..1_000).collect::<Vec<_>>());
+
+        let mut bytes = vec![];
+        filter.write_compressed_to(&mut bytes).unwrap();
+        let restored = BloomFilter::<u64>::read_compressed_from(&mut bytes.as_slice()).unwrap();
+
+        assert_eq!(restored.storage.as_bytes(), filter.storage.as_bytes());
+        assert_eq!(restored.get_layout(), Layout::Blocked);
+        assert_eq!(
+            restored.configuration.get_unique_entry_count(),
+            filter.configuration.get_unique_entry_count()
+        );
+        assert!((0..1_000).all(|entry| restored.contains(&entry)));
+
+        // A filter filled to 1% of its capacity compresses to a fraction of its storage
+        assert!(bytes.len() * 10 < filter.storage.as_bytes().len());
+    }
+
+    #[test]
+    fn should_load_compressed_filter_beyond_default_capacity_limit() {
+        let filter: BloomFilter<u64> = BloomFilter::builder()
+            .with_max_size(20_000_000)
+            .with_capacity_limit(20_000_000)
+            .build()
+            .unwrap();
+        filter.insert_many(&(0..1_000).collect::<Vec<_>>());
+
+        let mut bytes = vec![];
+        filter.write_compressed_to(&mut bytes).unwrap();
+        let restored = BloomFilter::<u64>::read_compressed_from(&mut bytes.as_slice()).unwrap();
+
+        assert_eq!(restored.get_total_bits(), filter.get_total_bits());
+        assert!((0..1_000).all(|entry| restored.contains(&entry)));
+    }
+
+    #[test]
+    fn should_return_err_when_compressed_filter_is_corrupted() {
+        let filter: BloomFilter = BloomFilter::builder().with_max_size(1_000).build().unwrap();
+        filter.insert("hello");
+
+        let mut bytes = vec![];
+        filter.write_compressed_to(&mut bytes).unwrap();
+        let last = bytes.len() - 9;
+        bytes[last] ^= 1;
+
+        assert!(BloomFilter::<str>::read_compressed_from(&mut bytes.as_slice()).is_err());
+    }
+
+    #[test]
+    fn should_load_compressed_filter_from_file() {
+        let path = std::env::temp_dir().join("bloom_filter_should_load_compressed_filter.bin");
+        let path = path.to_str().unwrap();
+
+        let filter: BloomFilter = BloomFilter::builder().with_max_size(1_000).build().unwrap();
+        filter.insert("hello");
+        filter.save_compressed(path).unwrap();
+
+        let restored = BloomFilter::<str>::load_compressed(path).unwrap();
+        let dispatched = super::load(path).unwrap();
+        std::fs::remove_file(path).unwrap();
+
+        assert!(restored.contains("hello"));
+        assert!(dispatched.contains("hello"));
+        assert!(!dispatched.contains("world"));
+    }
+
+    #[test]
+    fn should_contain_every_entry_after_folding() {
+        for layout in [Layout::Flat, Layout::Partitioned, Layout::Blocked] {
+            let filter: BloomFilter<u64> = BloomFilter::builder()
+                .with_max_size(10_000)
+                .with_total_bits(1 << 17)
+                .with_layout(layout)
+                .build()
+                .unwrap();
+            filter.insert_many(&(0..1_000).collect::<Vec<_>>());
+
+            let folded = filter.fold(4).unwrap();
+
+            assert_eq!(folded.get_total_bits(), 1 << 15);
+            assert!((0..1_000).all(|entry| folded.contains(&entry)));
+            assert!(folded.expected_false_positive_rate() > filter.expected_false_positive_rate());
+            assert!(folded.estimate_false_positive_rate() > filter.estimate_false_positive_rate());
+            assert!(folded.estimate_false_positive_rate() < 0.01);
+
+            let folded_twice = filter.fold(2).unwrap().fold(2).unwrap();
+            assert_eq!(folded_twice.storage.as_bytes(), folded.storage.as_bytes());
+            assert!(folded_twice.union(&folded).is_ok());
+        }
+    }
+
+    #[test]
+    fn should_keep_folding_after_round_trip() {
+        let filter: BloomFilter = BloomFilter::builder()
+            .with_max_size(1_000)
+            .with_total_bits(1 << 16)
+            .build()
+            .unwrap();
+        filter.insert("hello");
+
+        let folded = filter.fold(8).unwrap();
+
+        let mut bytes = vec![];
+        folded.write_to(&mut bytes).unwrap();
+        let restored = BloomFilter::<str>::read_from(&mut bytes.as_slice()).unwrap();
+
+        let mut compressed = vec![];
+        folded.write_compressed_to(&mut compressed).unwrap();
+        let decompressed =
+            BloomFilter::<str>::read_compressed_from(&mut compressed.as_slice()).unwrap();
+
+        for filter in [restored, decompressed] {
+            assert!(filter.contains("hello"));
+            assert_eq!(filter.get_total_bits(), 1 << 13);
+            assert!(filter.union(&folded).is_ok());
+        }
+    }
+
+    #[test]
+    fn should_return_err_when_fold_factor_is_invalid() {
+        let filter: BloomFilter = BloomFilter::builder()
+            .with_max_size(1_000)
+            .with_total_bits(1_000)
+            .build()
+            .unwrap();
+
+        for factor in [0, 1, 3, 16] {
+            assert!(matches!(
+                filter.fold(factor),
+                Err(BloomFilterError::Configuration(_))
+            ));
+        }
+
+        let same_size: BloomFilter = BloomFilter::builder()
+            .with_max_size(1_000)
+            .with_total_bits(500)
+            .build()
+            .unwrap();
+
+        assert!(matches!(
+            filter.fold(2).unwrap().union(&same_size),
+            Err(BloomFilterError::Incompatible(_))
+        ));
     }
 }
@@ -0,0 +1,124 @@
+use super::{
+    configurations::{Configuration, Limits},
+    hashing::strategy,
+    BloomFilterError, SupportedFloatingPointType,
+};
+
+// Parameters are the part of a builder that can be kept in a service config, they are validated the
+// same way a builder would validate them
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Parameters {
+    max_size: usize,
+    max_tolerance: SupportedFloatingPointType,
+    strategy_identifier: u8,
+    seed: u64,
+}
+
+impl Parameters {
+    pub fn try_from(
+        max_size: usize,
+        max_tolerance: SupportedFloatingPointType,
+        strategy_identifier: u8,
+        seed: u64,
+    ) -> Result<Self, BloomFilterError> {
+        // The capacity limit belongs to the builder the parameters end up in, only the storage stays bounded here
+        Configuration::try_from(
+            max_tolerance,
+            max_size,
+            &Limits::default().with_max_capacity(usize::MAX),
+        )
+        .map_err(BloomFilterError::Configuration)?;
+
+        if strategy::from_identifier(strategy_identifier, seed).is_none() {
+            return Err(BloomFilterError::Hashing(format!(
+                "Unknown hashing strategy {}",
+                strategy_identifier
+            )));
+        }
+
+        Ok(Self {
+            max_size,
+            max_tolerance,
+            strategy_identifier,
+            seed,
+        })
+    }
+
+    pub fn get_max_size(&self) -> usize {
+        self.max_size
+    }
+
+    pub fn get_max_tolerance(&self) -> SupportedFloatingPointType {
+        self.max_tolerance
+    }
+
+    pub fn get_strategy_identifier(&self) -> u8 {
+        self.strategy_identifier
+    }
+
+    pub fn get_seed(&self) -> u64 {
+        self.seed
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Parameters;
+    use crate::bloom_filters::{BloomFilter, BloomFilterError, Layout, SeaHashingStrategy};
+
+    #[test]
+    fn should_return_err_when_parameters_are_invalid() {
+        assert!(matches!(
+            Parameters::try_from(0, 0.01, 0, 29),
+            Err(BloomFilterError::Configuration(_))
+        ));
+        assert!(matches!(
+            Parameters::try_from(1_000, 1.5, 0, 29),
+            Err(BloomFilterError::Configuration(_))
+        ));
+        assert!(matches!(
+            Parameters::try_from(1_000, 0.01, u8::MAX, 29),
+            Err(BloomFilterError::Hashing(_))
+        ));
+    }
+
+    #[test]
+    fn should_build_the_same_filter_from_its_parameters() {
+        let filter: BloomFilter = BloomFilter::builder()
+            .with_max_size(1_000)
+            .with_max_tolerance(0.001)
+            .with_layout(Layout::Blocked)
+            .build()
+            .unwrap();
+        filter.insert("hello");
+
+        let parameters = filter.get_parameters().unwrap();
+        assert_eq!(
+            parameters,
+            Parameters::try_from(1_000, 0.001, 0, 29).unwrap()
+        );
+
+        let rebuilt: BloomFilter = BloomFilter::builder()
+            .with_parameters(&parameters)
+            .with_layout(Layout::Blocked)
+            .build()
+            .unwrap();
+
+        assert_eq!(rebuilt.get_total_bits(), filter.get_total_bits());
+        assert!(rebuilt.union(&filter).unwrap().contains("hello"));
+    }
+
+    #[test]
+    fn should_replace_sizing_and_strategy_chosen_before_parameters() {
+        let parameters = Parameters::try_from(1_000, 0.001, 0, 29).unwrap();
+
+        let filter: BloomFilter = BloomFilter::builder()
+            .with_total_bits(64)
+            .with_strategy(Box::new(SeaHashingStrategy::with_seed(7)))
+            .with_parameters(&parameters)
+            .build()
+            .unwrap();
+
+        assert_eq!(filter.get_parameters().unwrap(), parameters);
+    }
+}
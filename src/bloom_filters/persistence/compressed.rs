@@ -0,0 +1,203 @@
+use std::io::{Read, Write};
+
+use xxhash_rust::xxh3::xxh3_64;
+
+use super::{
+    golomb::{choose_rice_parameter, decode, encode},
+    read_array, Header,
+};
+
+// Compressed form of a filter for shipping, every field is stored in little-endian order:
+// magic bytes | version | header | rice parameter | set bits | encoded length | encoded set bits | checksum
+// the checksum is the xxh3 hash of every byte before it. Reading stops after the header, so the caller can
+// validate it before the encoded set bits are read
+pub(crate) struct Compressed {
+    pub(crate) header: Header,
+    rice_parameter: u8,
+    num_of_positions: usize,
+    encoded_length: usize,
+    prefix: Vec<u8>,
+}
+
+impl Compressed {
+    pub(crate) const MAGIC_BYTES: [u8; 4] = *b"BLMZ";
+    const FORMAT_VERSION: u16 = 1;
+    const PREFIX_SIZE: usize = 4 + 2 + Header::SIZE + 1 + 8 + 8;
+
+    pub(crate) fn write_to<W: Write>(
+        header: &Header,
+        positions: &[usize],
+        writer: &mut W,
+    ) -> Result<(), &'static str> {
+        let rice_parameter = choose_rice_parameter(positions.len(), header.total_bits as usize);
+        let encoded = encode(positions.iter().copied(), rice_parameter);
+
+        let mut bytes = Vec::with_capacity(Self::PREFIX_SIZE + encoded.len() + 8);
+        bytes.extend_from_slice(&Self::MAGIC_BYTES);
+        bytes.extend_from_slice(&Self::FORMAT_VERSION.to_le_bytes());
+        header.write_to(&mut bytes)?;
+        bytes.extend_from_slice(&rice_parameter.to_le_bytes());
+        bytes.extend_from_slice(&(positions.len() as u64).to_le_bytes());
+        bytes.extend_from_slice(&(encoded.len() as u64).to_le_bytes());
+        bytes.extend_from_slice(&encoded);
+        bytes.extend_from_slice(&xxh3_64(&bytes).to_le_bytes());
+
+        writer
+            .write_all(&bytes)
+            .map_err(|_| "Cannot write the compressed filter")
+    }
+
+    pub(crate) fn read_header_from<R: Read>(reader: &mut R) -> Result<Self, &'static str> {
+        let mut prefix = vec![0; Self::PREFIX_SIZE];
+        reader
+            .read_exact(&mut prefix)
+            .map_err(|_| "Header is truncated")?;
+
+        let mut fields = prefix.as_slice();
+
+        if read_array::<4, _>(&mut fields)? != Self::MAGIC_BYTES {
+            return Err("Not a compressed bloom filter file");
+        }
+
+        if u16::from_le_bytes(read_array(&mut fields)?) != Self::FORMAT_VERSION {
+            return Err("Unsupported format version");
+        }
+
+        let header = Header::read_from(&mut fields)?;
+        let rice_parameter = u8::from_le_bytes(read_array(&mut fields)?);
+        let num_of_positions = u64::from_le_bytes(read_array(&mut fields)?);
+        let encoded_length = u64::from_le_bytes(read_array(&mut fields)?);
+
+        let total_bits =
+            usize::try_from(header.total_bits).map_err(|_| "Total bits are too large")?;
+
+        if num_of_positions > header.total_bits {
+            return Err("Compressed filter is corrupted");
+        }
+
+        let num_of_positions = num_of_positions as usize;
+
+        if rice_parameter != choose_rice_parameter(num_of_positions, total_bits) {
+            return Err("Compressed filter is corrupted");
+        }
+
+        // Every position takes a stop bit and the remainder bits, the quotients add up to the total bits at most
+        let maximum_encoded_length = num_of_positions
+            .checked_mul(rice_parameter as usize + 1)
+            .and_then(|bits| bits.checked_add(total_bits >> rice_parameter))
+            .map(|bits| bits.div_ceil(8))
+            .ok_or("Compressed filter is corrupted")?;
+
+        if encoded_length > maximum_encoded_length as u64 {
+            return Err("Compressed filter is corrupted");
+        }
+
+        Ok(Self {
+            header,
+            rice_parameter,
+            num_of_positions,
+            encoded_length: encoded_length as usize,
+            prefix,
+        })
+    }
+
+    // The encoded set bits only grow with the bytes the stream actually holds
+    pub(crate) fn read_positions_from<R: Read>(
+        self,
+        reader: &mut R,
+    ) -> Result<Vec<usize>, &'static str> {
+        let mut bytes = self.prefix;
+        let prefix_size = bytes.len();
+
+        reader
+            .take(self.encoded_length as u64)
+            .read_to_end(&mut bytes)
+            .map_err(|_| "Cannot read the encoded positions")?;
+
+        if bytes.len() - prefix_size != self.encoded_length {
+            return Err("Encoded positions are truncated");
+        }
+
+        if u64::from_le_bytes(read_array(reader)?) != xxh3_64(&bytes) {
+            return Err("Checksum does not match");
+        }
+
+        decode(
+            &bytes[prefix_size..],
+            self.num_of_positions,
+            self.rice_parameter,
+            self.header.total_bits as usize,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Compressed;
+    use crate::bloom_filters::persistence::Header;
+
+    fn sample_header() -> Header {
+        Header {
+            total_bits: 10_000,
+            num_of_hash_funcs: 3,
+            seed: 29,
+            strategy_identifier: 0,
+            layout_identifier: 0,
+            fold_factor: 1,
+            max_size: 1_000,
+            max_tolerance: 0.1,
+            unique_entry_count: 3,
+        }
+    }
+
+    fn sample_bytes() -> Vec<u8> {
+        let mut bytes = vec![];
+        Compressed::write_to(&sample_header(), &[7, 42, 9_999], &mut bytes).unwrap();
+        bytes
+    }
+
+    fn read_from(bytes: &[u8]) -> Result<Vec<usize>, &'static str> {
+        let mut reader = bytes;
+        Compressed::read_header_from(&mut reader)?.read_positions_from(&mut reader)
+    }
+
+    #[test]
+    fn should_read_back_written_positions() {
+        let mut bytes = sample_bytes();
+        let mut reader = bytes.as_slice();
+        let compressed = Compressed::read_header_from(&mut reader).unwrap();
+
+        assert_eq!(compressed.header.total_bits, 10_000);
+        assert_eq!(compressed.header.unique_entry_count, 3);
+        assert_eq!(
+            compressed.read_positions_from(&mut reader).unwrap(),
+            vec![7, 42, 9_999]
+        );
+
+        // A longer encoding than the set bits can take is rejected before it is read
+        let length_offset = Compressed::PREFIX_SIZE - 8;
+        bytes[length_offset..Compressed::PREFIX_SIZE].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert!(Compressed::read_header_from(&mut bytes.as_slice()).is_err());
+    }
+
+    #[test]
+    fn should_return_err_when_any_byte_is_corrupted() {
+        let bytes = sample_bytes();
+
+        for idx in 0..bytes.len() {
+            let mut corrupted = bytes.clone();
+            corrupted[idx] ^= 0x10;
+
+            assert!(read_from(&corrupted).is_err());
+        }
+    }
+
+    #[test]
+    fn should_return_err_when_truncated() {
+        let bytes = sample_bytes();
+
+        for length in [0, 10, bytes.len() - 9, bytes.len() - 1] {
+            assert!(read_from(&bytes[..length]).is_err());
+        }
+    }
+}
@@ -0,0 +1,153 @@
+// Golomb–Rice coding of the gaps between set bits: every gap is split into a quotient written in
+// unary and a remainder written in rice_parameter bits, which suits the geometric gaps of a sparse filter
+pub(super) fn choose_rice_parameter(num_of_positions: usize, total_bits: usize) -> u8 {
+    if num_of_positions == 0 {
+        return 0;
+    }
+
+    let mean_gap = (total_bits - num_of_positions) as f64 / num_of_positions as f64;
+    let divisor = mean_gap * std::f64::consts::LN_2;
+
+    if divisor < 1.0 {
+        0
+    } else {
+        divisor.log2().floor().min(63.0) as u8
+    }
+}
+
+pub(super) fn encode<I: Iterator<Item = usize>>(positions: I, rice_parameter: u8) -> Vec<u8> {
+    let mut writer = BitWriter::default();
+    let mut next = 0;
+
+    positions.for_each(|position| {
+        let gap = (position - next) as u64;
+
+        (0..gap >> rice_parameter).for_each(|_| writer.write_bit(true));
+        writer.write_bit(false);
+        (0..rice_parameter)
+            .rev()
+            .for_each(|bit| writer.write_bit(gap >> bit & 1 == 1));
+
+        next = position + 1;
+    });
+
+    writer.bytes
+}
+
+pub(super) fn decode(
+    bytes: &[u8],
+    num_of_positions: usize,
+    rice_parameter: u8,
+    total_bits: usize,
+) -> Result<Vec<usize>, &'static str> {
+    let mut reader = BitReader { bytes, idx: 0 };
+    // Every position takes at least one bit, so the bytes bound what is reserved
+    let mut positions = Vec::with_capacity(num_of_positions.min(bytes.len() * 8));
+    let mut next = 0;
+
+    for _ in 0..num_of_positions {
+        let mut quotient = 0u64;
+        while reader.read_bit()? {
+            quotient += 1;
+        }
+
+        let remainder = (0..rice_parameter).try_fold(0u64, |remainder, _| {
+            Ok::<_, &'static str>(remainder << 1 | reader.read_bit()? as u64)
+        })?;
+
+        let position = (quotient << rice_parameter | remainder)
+            .checked_add(next as u64)
+            .filter(|&position| position < total_bits as u64)
+            .ok_or("Encoded position is out of range")? as usize;
+
+        positions.push(position);
+        next = position + 1;
+    }
+
+    Ok(positions)
+}
+
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    idx: usize,
+}
+
+impl BitWriter {
+    fn write_bit(&mut self, bit: bool) {
+        if self.idx.is_multiple_of(8) {
+            self.bytes.push(0);
+        }
+
+        if bit {
+            *self.bytes.last_mut().unwrap() |= 0x80 >> (self.idx % 8);
+        }
+
+        self.idx += 1;
+    }
+}
+
+struct BitReader<'a> {
+    bytes: &'a [u8],
+    idx: usize,
+}
+
+impl BitReader<'_> {
+    fn read_bit(&mut self) -> Result<bool, &'static str> {
+        let byte = self
+            .bytes
+            .get(self.idx / 8)
+            .ok_or("Encoded positions are truncated")?;
+        let bit = byte & (0x80 >> (self.idx % 8)) != 0;
+
+        self.idx += 1;
+
+        Ok(bit)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{choose_rice_parameter, decode, encode};
+
+    #[test]
+    fn should_decode_encoded_positions() {
+        let positions = vec![0, 1, 5, 64, 65, 1_000, 4_095];
+
+        for rice_parameter in [0, 3, 8] {
+            let bytes = encode(positions.iter().copied(), rice_parameter);
+
+            assert_eq!(
+                decode(&bytes, positions.len(), rice_parameter, 4_096).unwrap(),
+                positions
+            );
+        }
+    }
+
+    #[test]
+    fn should_pack_sparse_positions_tightly() {
+        let positions: Vec<usize> = (0..1_000).map(|idx| idx * 1_024 + idx % 7).collect();
+        let rice_parameter = choose_rice_parameter(positions.len(), 1_024_000);
+
+        let bytes = encode(positions.iter().copied(), rice_parameter);
+
+        // Raw storage would need 128,000 bytes, the gaps fit in a dozen bits each
+        assert!(bytes.len() < 1_000 * 12 / 8);
+    }
+
+    #[test]
+    fn should_return_err_when_positions_are_truncated_or_out_of_range() {
+        let positions = [3, 100, 2_000];
+        let bytes = encode(positions.iter().copied(), 4);
+
+        assert!(decode(&bytes[..bytes.len() - 1], 3, 4, 4_096).is_err());
+        assert!(decode(&bytes, 3, 4, 1_000).is_err());
+    }
+
+    #[test]
+    fn should_pick_rice_parameter_from_density() {
+        assert_eq!(choose_rice_parameter(0, 1_000), 0);
+        assert_eq!(choose_rice_parameter(500, 1_000), 0);
+        assert_eq!(choose_rice_parameter(1_000, 1_024_000), 9);
+    }
+}
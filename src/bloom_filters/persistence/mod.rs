@@ -0,0 +1,228 @@
+use std::io::{Read, Write};
+
+pub(super) use compressed::Compressed;
+
+mod compressed;
+mod golomb;
+
+// Header of the binary format, every field is stored in little-endian order:
+// magic bytes | version | total bits | hash functions | seed | strategy | layout | fold factor | max size | max tolerance | entries
+pub(super) struct Header {
+    pub(super) total_bits: u64,
+    pub(super) num_of_hash_funcs: u32,
+    pub(super) seed: u64,
+    pub(super) strategy_identifier: u8,
+    pub(super) layout_identifier: u8,
+    pub(super) fold_factor: u64,
+    pub(super) max_size: u64,
+    pub(super) max_tolerance: f64,
+    pub(super) unique_entry_count: u64,
+}
+
+impl Header {
+    pub(super) const MAGIC_BYTES: [u8; 4] = *b"BLMF";
+    const FORMAT_VERSION: u16 = 6;
+    pub(super) const SIZE: usize = 60;
+
+    pub(super) fn write_to<W: Write>(&self, writer: &mut W) -> Result<(), &'static str> {
+        let mut bytes = Vec::with_capacity(Self::SIZE);
+        bytes.extend_from_slice(&Self::MAGIC_BYTES);
+        bytes.extend_from_slice(&Self::FORMAT_VERSION.to_le_bytes());
+        bytes.extend_from_slice(&self.total_bits.to_le_bytes());
+        bytes.extend_from_slice(&self.num_of_hash_funcs.to_le_bytes());
+        bytes.extend_from_slice(&self.seed.to_le_bytes());
+        bytes.extend_from_slice(&self.strategy_identifier.to_le_bytes());
+        bytes.extend_from_slice(&self.layout_identifier.to_le_bytes());
+        bytes.extend_from_slice(&self.fold_factor.to_le_bytes());
+        bytes.extend_from_slice(&self.max_size.to_le_bytes());
+        bytes.extend_from_slice(&self.max_tolerance.to_le_bytes());
+        bytes.extend_from_slice(&self.unique_entry_count.to_le_bytes());
+
+        writer
+            .write_all(&bytes)
+            .map_err(|_| "Cannot write the header")
+    }
+
+    pub(super) fn read_from<R: Read>(reader: &mut R) -> Result<Self, &'static str> {
+        if read_array::<4, R>(reader)? != Self::MAGIC_BYTES {
+            return Err("Not a bloom filter file");
+        }
+
+        if u16::from_le_bytes(read_array(reader)?) != Self::FORMAT_VERSION {
+            return Err("Unsupported format version");
+        }
+
+        Ok(Self {
+            total_bits: u64::from_le_bytes(read_array(reader)?),
+            num_of_hash_funcs: u32::from_le_bytes(read_array(reader)?),
+            seed: u64::from_le_bytes(read_array(reader)?),
+            strategy_identifier: u8::from_le_bytes(read_array(reader)?),
+            layout_identifier: u8::from_le_bytes(read_array(reader)?),
+            fold_factor: u64::from_le_bytes(read_array(reader)?),
+            max_size: u64::from_le_bytes(read_array(reader)?),
+            max_tolerance: f64::from_le_bytes(read_array(reader)?),
+            unique_entry_count: u64::from_le_bytes(read_array(reader)?),
+        })
+    }
+}
+
+// Header of the binary format of static filters, every field is stored in little-endian order:
+// magic bytes | version | fingerprint bits | seed | strategy | construction seed | block length | entries
+pub(super) struct XorHeader {
+    pub(super) fingerprint_bits: u8,
+    pub(super) seed: u64,
+    pub(super) strategy_identifier: u8,
+    pub(super) construction_seed: u64,
+    pub(super) block_length: u64,
+    pub(super) unique_entry_count: u64,
+}
+
+impl XorHeader {
+    pub(super) const MAGIC_BYTES: [u8; 4] = *b"XORF";
+    const FORMAT_VERSION: u16 = 1;
+    pub(super) const SIZE: usize = 40;
+
+    pub(super) fn write_to<W: Write>(&self, writer: &mut W) -> Result<(), &'static str> {
+        let mut bytes = Vec::with_capacity(Self::SIZE);
+        bytes.extend_from_slice(&Self::MAGIC_BYTES);
+        bytes.extend_from_slice(&Self::FORMAT_VERSION.to_le_bytes());
+        bytes.extend_from_slice(&self.fingerprint_bits.to_le_bytes());
+        bytes.extend_from_slice(&self.seed.to_le_bytes());
+        bytes.extend_from_slice(&self.strategy_identifier.to_le_bytes());
+        bytes.extend_from_slice(&self.construction_seed.to_le_bytes());
+        bytes.extend_from_slice(&self.block_length.to_le_bytes());
+        bytes.extend_from_slice(&self.unique_entry_count.to_le_bytes());
+
+        writer
+            .write_all(&bytes)
+            .map_err(|_| "Cannot write the header")
+    }
+
+    pub(super) fn read_from<R: Read>(reader: &mut R) -> Result<Self, &'static str> {
+        if read_array::<4, R>(reader)? != Self::MAGIC_BYTES {
+            return Err("Not a static filter file");
+        }
+
+        if u16::from_le_bytes(read_array(reader)?) != Self::FORMAT_VERSION {
+            return Err("Unsupported format version");
+        }
+
+        Ok(Self {
+            fingerprint_bits: u8::from_le_bytes(read_array(reader)?),
+            seed: u64::from_le_bytes(read_array(reader)?),
+            strategy_identifier: u8::from_le_bytes(read_array(reader)?),
+            construction_seed: u64::from_le_bytes(read_array(reader)?),
+            block_length: u64::from_le_bytes(read_array(reader)?),
+            unique_entry_count: u64::from_le_bytes(read_array(reader)?),
+        })
+    }
+}
+
+fn read_array<const N: usize, R: Read>(reader: &mut R) -> Result<[u8; N], &'static str> {
+    let mut buffer = [0; N];
+    reader
+        .read_exact(&mut buffer)
+        .map_err(|_| "Header is truncated")?;
+    Ok(buffer)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Header, XorHeader};
+
+    fn sample_header() -> Header {
+        Header {
+            total_bits: 9585059,
+            num_of_hash_funcs: 7,
+            seed: 29,
+            strategy_identifier: 0,
+            layout_identifier: 2,
+            fold_factor: 4,
+            max_size: 1_000_000,
+            max_tolerance: 0.01,
+            unique_entry_count: 42,
+        }
+    }
+
+    #[test]
+    fn should_read_back_written_header() {
+        let mut bytes = vec![];
+        sample_header().write_to(&mut bytes).unwrap();
+
+        let header = Header::read_from(&mut bytes.as_slice()).unwrap();
+
+        assert_eq!(header.total_bits, 9585059);
+        assert_eq!(header.num_of_hash_funcs, 7);
+        assert_eq!(header.seed, 29);
+        assert_eq!(header.strategy_identifier, 0);
+        assert_eq!(header.layout_identifier, 2);
+        assert_eq!(header.fold_factor, 4);
+        assert_eq!(header.max_size, 1_000_000);
+        assert_eq!(header.max_tolerance, 0.01);
+        assert_eq!(header.unique_entry_count, 42);
+    }
+
+    #[test]
+    fn should_return_err_when_magic_bytes_are_wrong() {
+        let mut bytes = vec![];
+        sample_header().write_to(&mut bytes).unwrap();
+        bytes[0] = b'X';
+
+        assert!(Header::read_from(&mut bytes.as_slice()).is_err());
+    }
+
+    #[test]
+    fn should_return_err_when_version_is_unsupported() {
+        let mut bytes = vec![];
+        sample_header().write_to(&mut bytes).unwrap();
+        bytes[4] = u8::MAX;
+
+        assert!(Header::read_from(&mut bytes.as_slice()).is_err());
+    }
+
+    #[test]
+    fn should_return_err_when_header_is_truncated() {
+        let mut bytes = vec![];
+        sample_header().write_to(&mut bytes).unwrap();
+        bytes.truncate(20);
+
+        assert!(Header::read_from(&mut bytes.as_slice()).is_err());
+    }
+
+    fn sample_xor_header() -> XorHeader {
+        XorHeader {
+            fingerprint_bits: 16,
+            seed: 29,
+            strategy_identifier: 1,
+            construction_seed: 7,
+            block_length: 420,
+            unique_entry_count: 1_000,
+        }
+    }
+
+    #[test]
+    fn should_read_back_written_xor_header() {
+        let mut bytes = vec![];
+        sample_xor_header().write_to(&mut bytes).unwrap();
+
+        assert_eq!(bytes.len(), XorHeader::SIZE);
+
+        let header = XorHeader::read_from(&mut bytes.as_slice()).unwrap();
+
+        assert_eq!(header.fingerprint_bits, 16);
+        assert_eq!(header.seed, 29);
+        assert_eq!(header.strategy_identifier, 1);
+        assert_eq!(header.construction_seed, 7);
+        assert_eq!(header.block_length, 420);
+        assert_eq!(header.unique_entry_count, 1_000);
+    }
+
+    #[test]
+    fn should_return_err_when_magic_bytes_belong_to_another_filter() {
+        let mut bytes = vec![];
+        sample_xor_header().write_to(&mut bytes).unwrap();
+        bytes[..4].copy_from_slice(b"BLMF");
+
+        assert!(XorHeader::read_from(&mut bytes.as_slice()).is_err());
+    }
+}
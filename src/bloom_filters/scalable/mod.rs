@@ -0,0 +1,307 @@
+use std::{
+    hash::Hash,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        PoisonError, RwLock, RwLockReadGuard,
+    },
+};
+
+use super::{
+    builder::Builder,
+    configurations::{ConfigError, Configurable, Configuration, Limits, Tolerance},
+    hashing::strategy::{self, Hashing},
+    BloomFilter, BloomFilterError, Layout, SupportedFloatingPointType,
+};
+
+// ScalableBloomFilter follows Almeida et al.: layer i holds up to max_size * GROWTH_FACTOR^i entries
+// with a tolerance of max_tolerance * (1 - TIGHTENING_RATIO) * TIGHTENING_RATIO^i, so the sum of
+// the tolerances of all layers never exceeds max_tolerance
+pub struct ScalableBloomFilter<T: ?Sized = str> {
+    layers: RwLock<Vec<BloomFilter<T>>>,
+    current_layer_size: AtomicUsize,
+    max_tolerance: SupportedFloatingPointType,
+}
+
+impl<T: Hash + ?Sized> ScalableBloomFilter<T> {
+    const GROWTH_FACTOR: usize = 2;
+    const TIGHTENING_RATIO: SupportedFloatingPointType = 0.85;
+
+    pub fn builder() -> Builder<Self> {
+        Builder::default()
+    }
+
+    pub(super) fn try_from(
+        max_size: usize,
+        max_tolerance: SupportedFloatingPointType,
+        strategy: Option<Box<dyn Hashing>>,
+        limits: &Limits,
+    ) -> Result<Self, BloomFilterError> {
+        // The tolerances of the layers only add up to the max tolerance, so it has to be valid on its own
+        Tolerance::try_from(max_tolerance)
+            .map_err(String::from)
+            .map_err(ConfigError::Validation)
+            .map_err(BloomFilterError::Configuration)?;
+
+        let first_layer = Self::try_layer_from(
+            max_size,
+            max_tolerance * (1.0 - Self::TIGHTENING_RATIO),
+            strategy,
+            limits,
+        )?;
+
+        first_layer
+            .manager
+            .try_restore_strategy()
+            .map_err(String::from)
+            .map_err(BloomFilterError::Hashing)?;
+
+        Ok(Self {
+            layers: RwLock::new(vec![first_layer]),
+            current_layer_size: AtomicUsize::new(0),
+            max_tolerance,
+        })
+    }
+
+    pub fn insert(&self, entry: &T) -> Result<(), BloomFilterError> {
+        if self.contains(entry) {
+            return Ok(());
+        }
+
+        if self.is_current_layer_full(&self.read_layers()) {
+            self.add_layer()?;
+        }
+
+        if let Some(layer) = self.read_layers().last() {
+            layer.insert(entry);
+        }
+
+        self.current_layer_size.fetch_add(1, Ordering::Relaxed);
+
+        Ok(())
+    }
+
+    pub fn contains(&self, entry: &T) -> bool {
+        self.read_layers()
+            .iter()
+            .rev()
+            .any(|layer| layer.contains(entry))
+    }
+
+    fn read_layers(&self) -> RwLockReadGuard<'_, Vec<BloomFilter<T>>> {
+        self.layers.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn is_current_layer_full(&self, layers: &[BloomFilter<T>]) -> bool {
+        layers.last().is_none_or(|layer| {
+            self.current_layer_size.load(Ordering::Relaxed) >= layer.configuration.get_max_size()
+        })
+    }
+
+    fn add_layer(&self) -> Result<(), BloomFilterError> {
+        let mut layers = self.layers.write().unwrap_or_else(PoisonError::into_inner);
+
+        // Another thread may have grown the filter while this one was waiting for the lock
+        if !self.is_current_layer_full(&layers) {
+            return Ok(());
+        }
+
+        // Every layer hashes with a seed of its own, the limits only bound the first layer so growth never stops
+        let (max_size, identifier, seed, limits) = match layers.last() {
+            Some(layer) => (
+                layer.configuration.get_max_size() * Self::GROWTH_FACTOR,
+                layer.manager.get_strategy_identifier(),
+                layer.manager.get_seed().wrapping_add(1),
+                layer
+                    .configuration
+                    .get_limits()
+                    .with_max_capacity(usize::MAX),
+            ),
+            None => {
+                return Err(BloomFilterError::Storage(String::from(
+                    "Filter has no layer to grow from",
+                )))
+            }
+        };
+
+        let max_tolerance = self.max_tolerance
+            * (1.0 - Self::TIGHTENING_RATIO)
+            * Self::TIGHTENING_RATIO.powi(layers.len() as i32);
+
+        let strategy = strategy::from_identifier(identifier, seed).ok_or_else(|| {
+            BloomFilterError::Hashing(String::from(
+                "Hashing strategy cannot be restored for new layers",
+            ))
+        })?;
+
+        layers.push(Self::try_layer_from(
+            max_size,
+            max_tolerance,
+            Some(strategy),
+            &limits,
+        )?);
+
+        self.current_layer_size.store(0, Ordering::Relaxed);
+
+        Ok(())
+    }
+
+    fn try_layer_from(
+        max_size: usize,
+        max_tolerance: SupportedFloatingPointType,
+        strategy: Option<Box<dyn Hashing>>,
+        limits: &Limits,
+    ) -> Result<BloomFilter<T>, BloomFilterError> {
+        let configuration = Configuration::try_from(max_tolerance, max_size, limits)
+            .map_err(BloomFilterError::Configuration)?;
+
+        BloomFilter::try_from(configuration, strategy, Layout::Flat)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::atomic::Ordering;
+
+    use super::{BloomFilterError, ConfigError, Hashing, ScalableBloomFilter};
+
+    #[test]
+    fn should_add_layers_when_current_layer_reaches_its_capacity() {
+        let filter: ScalableBloomFilter = ScalableBloomFilter::builder()
+            .with_max_size(100)
+            .build()
+            .unwrap();
+
+        (0..100).for_each(|idx| filter.insert(&format!("word-{}", idx)).unwrap());
+        assert_eq!(filter.read_layers().len(), 1);
+
+        filter.insert("one more").unwrap();
+        assert_eq!(filter.read_layers().len(), 2);
+
+        (100..1_000).for_each(|idx| filter.insert(&format!("word-{}", idx)).unwrap());
+        assert!((0..1_000).all(|idx| filter.contains(&format!("word-{}", idx))));
+    }
+
+    #[test]
+    fn should_keep_growing_past_capacity_limit() {
+        let filter: ScalableBloomFilter = ScalableBloomFilter::builder()
+            .with_max_size(100)
+            .with_capacity_limit(150)
+            .build()
+            .unwrap();
+
+        (0..2_000).for_each(|idx| filter.insert(&format!("word-{}", idx)).unwrap());
+
+        assert!(filter.read_layers().len() > 3);
+        assert!((0..2_000).all(|idx| filter.contains(&format!("word-{}", idx))));
+    }
+
+    #[test]
+    fn should_hash_every_layer_with_its_own_seed() {
+        let filter: ScalableBloomFilter = ScalableBloomFilter::builder()
+            .with_max_size(10)
+            .build()
+            .unwrap();
+
+        (0..100).for_each(|idx| filter.insert(&format!("word-{}", idx)).unwrap());
+
+        let mut seeds: Vec<u64> = filter
+            .read_layers()
+            .iter()
+            .map(|layer| layer.manager.get_seed())
+            .collect();
+        let num_of_layers = seeds.len();
+        seeds.dedup();
+
+        assert!(num_of_layers > 1);
+        assert_eq!(seeds.len(), num_of_layers);
+    }
+
+    #[test]
+    fn should_return_err_when_strategy_cannot_be_restored_for_new_layers() {
+        struct CustomHashingStrategy;
+
+        impl Hashing for CustomHashingStrategy {
+            fn hash(&self, entry: &[u8]) -> (u64, u64) {
+                (entry.len() as u64, 1)
+            }
+        }
+
+        let maybe_filter = ScalableBloomFilter::<str>::builder()
+            .with_strategy(Box::new(CustomHashingStrategy))
+            .build();
+
+        assert!(matches!(maybe_filter, Err(BloomFilterError::Hashing(_))));
+    }
+
+    #[test]
+    fn should_return_err_when_sizing_is_invalid() {
+        let builders = [
+            ScalableBloomFilter::<str>::builder().with_max_tolerance(5.0),
+            ScalableBloomFilter::<str>::builder().with_num_of_hash_funcs(3),
+            ScalableBloomFilter::<str>::builder()
+                .with_max_tolerance(0.01)
+                .with_num_of_hash_funcs(3),
+        ];
+
+        for builder in builders {
+            assert!(matches!(
+                builder.build(),
+                Err(BloomFilterError::Configuration(ConfigError::Validation(_)))
+            ));
+        }
+    }
+
+    #[test]
+    fn should_not_count_duplicates_towards_capacity() {
+        let filter: ScalableBloomFilter = ScalableBloomFilter::builder()
+            .with_max_size(10)
+            .build()
+            .unwrap();
+
+        (0..100).for_each(|_| filter.insert("hello").unwrap());
+
+        assert_eq!(filter.read_layers().len(), 1);
+        assert_eq!(filter.current_layer_size.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn should_keep_false_positive_rate_within_tolerance_past_capacity() {
+        let filter: ScalableBloomFilter = ScalableBloomFilter::builder()
+            .with_max_size(1_000)
+            .with_max_tolerance(0.01)
+            .build()
+            .unwrap();
+
+        (0..20_000).for_each(|idx| filter.insert(&format!("member-{}", idx)).unwrap());
+
+        let false_positives = (0..20_000)
+            .filter(|idx| filter.contains(&format!("stranger-{}", idx)))
+            .count();
+
+        assert!(filter.read_layers().len() > 1);
+        assert!((false_positives as f64) / 20_000.0 <= 0.01);
+    }
+
+    #[test]
+    fn should_grow_while_inserted_from_many_threads() {
+        let filter: ScalableBloomFilter = ScalableBloomFilter::builder()
+            .with_max_size(100)
+            .build()
+            .unwrap();
+
+        std::thread::scope(|scope| {
+            for thread in 0..4 {
+                let filter = &filter;
+                scope.spawn(move || {
+                    (0..500)
+                        .for_each(|idx| filter.insert(&format!("word-{}-{}", thread, idx)).unwrap())
+                });
+            }
+        });
+
+        assert!(filter.read_layers().len() > 1);
+        assert!((0..4).all(|thread| {
+            (0..500).all(|idx| filter.contains(&format!("word-{}-{}", thread, idx)))
+        }));
+    }
+}
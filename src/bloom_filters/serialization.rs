@@ -0,0 +1,195 @@
+use std::{hash::Hash, marker::PhantomData};
+
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+use super::{
+    configurations::{Configurable, Limits},
+    persistence::Header,
+    restore_from,
+    storage::Storage,
+    BloomFilter, Parameters, SupportedFloatingPointType,
+};
+
+// Both forms mirror the binary format, deserializing goes through the same validation as loading a file
+#[derive(Serialize, Deserialize)]
+struct SerializedParameters {
+    max_size: usize,
+    max_tolerance: SupportedFloatingPointType,
+    strategy: u8,
+    seed: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SerializedBloomFilter {
+    total_bits: u64,
+    num_of_hash_funcs: u32,
+    seed: u64,
+    strategy: u8,
+    layout: u8,
+    fold_factor: u64,
+    max_size: u64,
+    max_tolerance: f64,
+    unique_entry_count: u64,
+    storage: Vec<u8>,
+}
+
+impl Serialize for Parameters {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        SerializedParameters {
+            max_size: self.get_max_size(),
+            max_tolerance: self.get_max_tolerance(),
+            strategy: self.get_strategy_identifier(),
+            seed: self.get_seed(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Parameters {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let parameters = SerializedParameters::deserialize(deserializer)?;
+
+        Parameters::try_from(
+            parameters.max_size,
+            parameters.max_tolerance,
+            parameters.strategy,
+            parameters.seed,
+        )
+        .map_err(|err| D::Error::custom(format!("{:?}", err)))
+    }
+}
+
+impl<T: Hash + ?Sized> Serialize for BloomFilter<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let header = self
+            .header()
+            .map_err(|err| serde::ser::Error::custom(format!("{:?}", err)))?;
+
+        SerializedBloomFilter {
+            total_bits: header.total_bits,
+            num_of_hash_funcs: header.num_of_hash_funcs,
+            seed: header.seed,
+            strategy: header.strategy_identifier,
+            layout: header.layout_identifier,
+            fold_factor: header.fold_factor,
+            max_size: header.max_size,
+            max_tolerance: header.max_tolerance,
+            unique_entry_count: header.unique_entry_count,
+            storage: self.storage.as_bytes(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, T: Hash + ?Sized> Deserialize<'de> for BloomFilter<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let filter = SerializedBloomFilter::deserialize(deserializer)?;
+
+        let header = Header {
+            total_bits: filter.total_bits,
+            num_of_hash_funcs: filter.num_of_hash_funcs,
+            seed: filter.seed,
+            strategy_identifier: filter.strategy,
+            layout_identifier: filter.layout,
+            fold_factor: filter.fold_factor,
+            max_size: filter.max_size,
+            max_tolerance: filter.max_tolerance,
+            unique_entry_count: filter.unique_entry_count,
+        };
+
+        let (configuration, manager) = restore_from(&header, &Limits::unbounded())
+            .map_err(|err| D::Error::custom(format!("{:?}", err)))?;
+
+        let storage = Storage::try_from_bytes(configuration.get_total_bits(), filter.storage)
+            .map_err(D::Error::custom)?;
+
+        Ok(Self {
+            configuration,
+            manager,
+            storage,
+            entry: PhantomData,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::bloom_filters::{BloomFilter, Layout, Parameters};
+
+    #[test]
+    fn should_round_trip_parameters_through_json() {
+        let parameters = Parameters::try_from(1_000, 0.001, 1, 7).unwrap();
+
+        let json = serde_json::to_string(&parameters).unwrap();
+        assert_eq!(
+            json,
+            r#"{"max_size":1000,"max_tolerance":0.001,"strategy":1,"seed":7}"#
+        );
+
+        let restored: Parameters = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, parameters);
+    }
+
+    #[test]
+    fn should_return_err_when_deserialized_parameters_are_invalid() {
+        for json in [
+            r#"{"max_size":0,"max_tolerance":0.001,"strategy":1,"seed":7}"#,
+            r#"{"max_size":1000,"max_tolerance":2.0,"strategy":1,"seed":7}"#,
+            r#"{"max_size":1000,"max_tolerance":0.001,"strategy":200,"seed":7}"#,
+            r#"{"max_size":1000,"strategy":1,"seed":7}"#,
+        ] {
+            assert!(serde_json::from_str::<Parameters>(json).is_err());
+        }
+    }
+
+    #[test]
+    fn should_round_trip_filter_through_json_and_bincode() {
+        let filter: BloomFilter = BloomFilter::builder()
+            .with_max_size(100)
+            .with_layout(Layout::Partitioned)
+            .build()
+            .unwrap();
+        filter.insert_many(["hello", "world"]);
+
+        let json = serde_json::to_string(&filter).unwrap();
+        let from_json: BloomFilter = serde_json::from_str(&json).unwrap();
+
+        let bytes = bincode::serialize(&filter).unwrap();
+        let from_bincode: BloomFilter = bincode::deserialize(&bytes).unwrap();
+
+        for restored in [from_json, from_bincode] {
+            assert!(restored.contains("hello"));
+            assert!(restored.contains("world"));
+            assert_eq!(restored.get_layout(), Layout::Partitioned);
+            assert_eq!(restored.storage.as_bytes(), filter.storage.as_bytes());
+            assert!(restored.union(&filter).is_ok());
+        }
+    }
+
+    #[test]
+    fn should_return_err_when_deserialized_filter_is_inconsistent() {
+        let filter: BloomFilter = BloomFilter::builder().with_max_size(100).build().unwrap();
+        let json = serde_json::to_value(&filter).unwrap();
+
+        let mut corruptions = vec![];
+        for (field, value) in [
+            ("total_bits", serde_json::json!(0)),
+            ("total_bits", serde_json::json!(1u64 << 60)),
+            ("num_of_hash_funcs", serde_json::json!(0)),
+            ("num_of_hash_funcs", serde_json::json!(u32::MAX)),
+            ("strategy", serde_json::json!(200)),
+            ("layout", serde_json::json!(9)),
+            ("fold_factor", serde_json::json!(3)),
+            ("max_tolerance", serde_json::json!(0.0)),
+            ("storage", serde_json::json!([0, 0])),
+        ] {
+            let mut corrupted = json.clone();
+            corrupted[field] = value;
+            corruptions.push(corrupted);
+        }
+
+        for corrupted in corruptions {
+            assert!(serde_json::from_value::<BloomFilter>(corrupted).is_err());
+        }
+    }
+}
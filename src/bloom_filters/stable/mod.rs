@@ -0,0 +1,296 @@
+use std::{
+    hash::Hash,
+    marker::PhantomData,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use super::{
+    builder::Builder,
+    configurations::{ConfigError, Configurable, Configuration},
+    hashing::{
+        strategy::{remix, Hashing},
+        HashManager,
+    },
+    manage_hashing,
+    storage::CounterStorage,
+    BloomFilterError, Layout, SupportedFloatingPointType,
+};
+
+// StableBloomFilter follows Deng & Rafiei: every insert decays P random cells before setting the k
+// cells of the entry to max_cell_value, so the share of zero cells converges instead of reaching 0
+pub struct StableBloomFilter<T: ?Sized = str> {
+    configuration: Configuration,
+    manager: HashManager,
+    storage: CounterStorage,
+    max_cell_value: u8,
+    num_of_decayed_cells: usize,
+    random_state: AtomicU64,
+    entry: PhantomData<fn(&T)>,
+}
+
+impl<T: Hash + ?Sized> StableBloomFilter<T> {
+    pub(super) const DEFAULT_MAX_CELL_VALUE: u8 = 3;
+
+    pub fn builder() -> Builder<Self> {
+        Builder::default()
+    }
+
+    pub(super) fn try_from(
+        configuration: Configuration,
+        strategy: Option<Box<dyn Hashing>>,
+        max_cell_value: u8,
+    ) -> Result<Self, BloomFilterError> {
+        if max_cell_value == 0 || max_cell_value > CounterStorage::MAXIMUM_COUNTER_VALUE {
+            return Err(BloomFilterError::Configuration(ConfigError::Validation(
+                format!(
+                    "Max cell value must be within 1 and {}",
+                    CounterStorage::MAXIMUM_COUNTER_VALUE
+                ),
+            )));
+        }
+
+        let manager = manage_hashing(&configuration, strategy, Layout::Flat)?;
+
+        let storage = CounterStorage::try_from(configuration.get_total_bits())
+            .map_err(String::from)
+            .map_err(BloomFilterError::Storage)?;
+
+        let num_of_decayed_cells = calculate_number_of_decayed_cells(
+            configuration.get_max_tolerance(),
+            configuration.get_num_of_hash_funcs(),
+            configuration.get_total_bits(),
+            max_cell_value,
+        )
+        .map_err(|message| ConfigError::Dependency(String::from(message)))
+        .map_err(BloomFilterError::Configuration)?;
+
+        let random_state = AtomicU64::new(remix(manager.get_seed()));
+
+        Ok(Self {
+            configuration,
+            manager,
+            storage,
+            max_cell_value,
+            num_of_decayed_cells,
+            random_state,
+            entry: PhantomData,
+        })
+    }
+
+    pub fn insert(&self, entry: &T) {
+        let total_cells = self.configuration.get_total_bits() as u64;
+
+        (0..self.num_of_decayed_cells).for_each(|_| {
+            self.storage
+                .decay_at((self.next_random() % total_cells) as usize);
+        });
+
+        self.manager.hash(entry).for_each(|idx| {
+            self.storage.set_at(idx, self.max_cell_value);
+        });
+    }
+
+    pub fn contains(&self, entry: &T) -> bool {
+        self.manager
+            .hash(entry)
+            .all(|idx| self.storage.read_counter_at(idx) != 0)
+    }
+
+    pub fn get_num_of_decayed_cells(&self) -> usize {
+        self.num_of_decayed_cells
+    }
+
+    // Share of zero cells in the stable state: (1 / (1 + 1 / (P * (1/k - 1/m))))^Max
+    fn stable_zero_ratio(&self) -> SupportedFloatingPointType {
+        let num_of_hash_funcs =
+            self.configuration.get_num_of_hash_funcs() as SupportedFloatingPointType;
+        let total_cells = self.configuration.get_total_bits() as SupportedFloatingPointType;
+        let decay = self.num_of_decayed_cells as SupportedFloatingPointType
+            * (1.0 / num_of_hash_funcs - 1.0 / total_cells);
+
+        (1.0 / (1.0 + 1.0 / decay)).powi(self.max_cell_value as i32)
+    }
+
+    pub fn stable_false_positive_rate(&self) -> SupportedFloatingPointType {
+        (1.0 - self.stable_zero_ratio()).powi(self.configuration.get_num_of_hash_funcs() as i32)
+    }
+
+    // Upper bound for an entry last inserted gap inserts ago, it ignores other entries setting its cells again
+    pub fn stable_false_negative_rate(&self, gap: usize) -> SupportedFloatingPointType {
+        let decay_probability = self.num_of_decayed_cells as SupportedFloatingPointType
+            / self.configuration.get_total_bits() as SupportedFloatingPointType;
+
+        let cell_cleared_probability =
+            calculate_binomial_tail(gap, decay_probability, self.max_cell_value as usize);
+
+        1.0 - (1.0 - cell_cleared_probability)
+            .powi(self.configuration.get_num_of_hash_funcs() as i32)
+    }
+
+    // Every call advances one shared splitmix64 sequence, so concurrent inserts still draw distinct values
+    fn next_random(&self) -> u64 {
+        remix(
+            self.random_state
+                .fetch_add(0x9e37_79b9_7f4a_7c15, Ordering::Relaxed),
+        )
+    }
+}
+
+// P = 1 / ((1 / (1 - p^(1/k))^(1/Max) - 1) * (1/k - 1/m)) makes the stable false positive rate reach p,
+// decaying more cells than the filter has would only clear the same cells again
+fn calculate_number_of_decayed_cells(
+    max_tolerance: SupportedFloatingPointType,
+    num_of_hash_funcs: usize,
+    total_cells: usize,
+    max_cell_value: u8,
+) -> Result<usize, &'static str> {
+    let num_of_hash_funcs = num_of_hash_funcs as SupportedFloatingPointType;
+    let zero_ratio = 1.0 - max_tolerance.powf(1.0 / num_of_hash_funcs);
+    let denominator = ((1.0 / zero_ratio).powf(1.0 / max_cell_value as SupportedFloatingPointType)
+        - 1.0)
+        * (1.0 / num_of_hash_funcs - 1.0 / total_cells as SupportedFloatingPointType);
+
+    if !denominator.is_finite() || denominator <= 0.0 {
+        return Err(
+            "No number of decayed cells reaches the tolerance with these cells and hash functions",
+        );
+    }
+
+    Ok(((1.0 / denominator).round() as usize).clamp(1, total_cells))
+}
+
+// Probability of at least `successes` successes out of `trials`, each succeeding with `probability`
+fn calculate_binomial_tail(
+    trials: usize,
+    probability: SupportedFloatingPointType,
+    successes: usize,
+) -> SupportedFloatingPointType {
+    if successes > trials {
+        return 0.0;
+    }
+
+    let mut term = (1.0 - probability).powf(trials as SupportedFloatingPointType);
+    let mut below = 0.0;
+
+    for idx in 0..successes {
+        below += term;
+        term *= (trials - idx) as SupportedFloatingPointType
+            / (idx + 1) as SupportedFloatingPointType
+            * probability
+            / (1.0 - probability);
+    }
+
+    (1.0 - below).clamp(0.0, 1.0)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::bloom_filters::{configurations::Configurable, BloomFilterError};
+
+    use super::StableBloomFilter;
+
+    fn build_filter() -> StableBloomFilter<u64> {
+        StableBloomFilter::builder()
+            .with_max_size(1_000)
+            .with_max_tolerance(0.01)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn should_contain_recently_inserted_entries() {
+        let filter: StableBloomFilter = StableBloomFilter::builder()
+            .with_max_size(1_000)
+            .build()
+            .unwrap();
+
+        filter.insert("hello");
+
+        assert!(filter.contains("hello"));
+        assert!(!filter.contains("world"));
+    }
+
+    #[test]
+    fn should_converge_to_stable_false_positive_rate_on_unbounded_stream() {
+        let filter = build_filter();
+
+        (0..200_000u64).for_each(|entry| filter.insert(&entry));
+
+        let false_positives = (1_000_000..1_100_000u64)
+            .filter(|entry| filter.contains(entry))
+            .count();
+        let rate = false_positives as f64 / 100_000.0;
+
+        assert!((filter.stable_false_positive_rate() - 0.01).abs() < 0.002);
+        assert!(rate < filter.stable_false_positive_rate() * 1.5);
+
+        let total_cells = filter.configuration.get_total_bits();
+        let zero_cells = (0..total_cells)
+            .filter(|&idx| filter.storage.read_counter_at(idx) == 0)
+            .count();
+
+        assert!((zero_cells as f64 / total_cells as f64 - filter.stable_zero_ratio()).abs() < 0.05);
+    }
+
+    #[test]
+    fn should_forget_entries_of_the_distant_past() {
+        let filter = build_filter();
+
+        (0..100_000u64).for_each(|entry| filter.insert(&entry));
+
+        let remembered_recent = (99_900..100_000u64)
+            .filter(|entry| filter.contains(entry))
+            .count();
+        let remembered_old = (0..100u64).filter(|entry| filter.contains(entry)).count();
+
+        assert!(remembered_recent > 95);
+        assert!(remembered_old < 10);
+    }
+
+    #[test]
+    fn should_estimate_false_negative_rate_growing_with_gap() {
+        let filter = build_filter();
+
+        assert_eq!(filter.stable_false_negative_rate(0), 0.0);
+
+        let rates: Vec<f64> = [10, 100, 1_000, 10_000, 100_000]
+            .iter()
+            .map(|&gap| filter.stable_false_negative_rate(gap))
+            .collect();
+
+        assert!(rates.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert!(rates[0] < 0.001);
+        assert!(rates[4] > 0.999);
+    }
+
+    #[test]
+    fn should_return_err_when_every_cell_is_probed() {
+        let maybe_filter = StableBloomFilter::<str>::builder()
+            .with_max_size(1)
+            .with_total_bits(1)
+            .build();
+
+        assert!(matches!(
+            maybe_filter,
+            Err(BloomFilterError::Configuration(_))
+        ));
+    }
+
+    #[test]
+    fn should_decay_at_most_every_cell() {
+        let num_of_decayed_cells = super::calculate_number_of_decayed_cells(0.5, 1, 4, 15).unwrap();
+
+        assert!(num_of_decayed_cells <= 4);
+    }
+
+    #[test]
+    fn should_return_err_when_max_cell_value_is_invalid() {
+        for max_cell_value in [0, 16] {
+            let maybe_filter = StableBloomFilter::<str>::builder()
+                .with_max_cell_value(max_cell_value)
+                .build();
+
+            assert!(maybe_filter.is_err());
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use super::SupportedFloatingPointType;
+
+// Swamidass & Baldi: n* = -(m / k) * ln(1 - X / m), where X is the number of set bits
+pub(super) fn estimate_cardinality_based_on_set_bits(
+    total_bits: usize,
+    num_of_hash_funcs: usize,
+    set_bits: usize,
+) -> SupportedFloatingPointType {
+    if set_bits >= total_bits {
+        return SupportedFloatingPointType::INFINITY;
+    }
+
+    let total_bits = total_bits as SupportedFloatingPointType;
+
+    -(total_bits / num_of_hash_funcs as SupportedFloatingPointType)
+        * (1.0 - set_bits as SupportedFloatingPointType / total_bits).ln()
+}
+
+pub(super) fn estimate_false_positive_rate_based_on_fill_ratio(
+    fill_ratio: SupportedFloatingPointType,
+    num_of_hash_funcs: usize,
+) -> SupportedFloatingPointType {
+    fill_ratio.powi(num_of_hash_funcs as i32)
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn should_estimate_cardinality_from_set_bits() {
+        let test_cases = vec![(1_000, 1, 0, 0), (1_000, 1, 1, 1), (9_585, 7, 5_000, 1_010)];
+
+        for (total_bits, num_of_hash_funcs, set_bits, expected_cardinality) in test_cases {
+            let cardinality = super::estimate_cardinality_based_on_set_bits(
+                total_bits,
+                num_of_hash_funcs,
+                set_bits,
+            );
+            assert_eq!(cardinality.round() as usize, expected_cardinality);
+        }
+    }
+
+    #[test]
+    fn should_estimate_infinite_cardinality_when_every_bit_is_set() {
+        let cardinality = super::estimate_cardinality_based_on_set_bits(8, 3, 8);
+        assert!(cardinality.is_infinite());
+    }
+
+    #[test]
+    fn should_estimate_false_positive_rate_from_fill_ratio() {
+        let test_cases = vec![
+            (0.0, 7, 0.0),
+            (0.5, 1, 0.5),
+            (0.5, 7, 0.0078125),
+            (1.0, 7, 1.0),
+        ];
+
+        for (fill_ratio, num_of_hash_funcs, expected_rate) in test_cases {
+            let rate = super::estimate_false_positive_rate_based_on_fill_ratio(
+                fill_ratio,
+                num_of_hash_funcs,
+            );
+            assert_eq!(rate, expected_rate);
+        }
+    }
+}
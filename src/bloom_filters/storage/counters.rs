@@ -0,0 +1,123 @@
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use super::utility::{
+    calculate_storage_capacity_based_on_total_counters, read_counter_coordinates, BITS_PER_COUNTER,
+};
+
+pub(crate) struct CounterStorage(Vec<AtomicU8>);
+
+impl CounterStorage {
+    const COUNTER_MASK: u8 = (1 << BITS_PER_COUNTER) - 1;
+    pub(crate) const MAXIMUM_COUNTER_VALUE: u8 = Self::COUNTER_MASK;
+
+    pub(crate) fn try_from(total_counters: usize) -> Result<Self, &'static str> {
+        if total_counters == 0 {
+            return Err("Total counters must be positive");
+        }
+
+        let capacity = calculate_storage_capacity_based_on_total_counters(total_counters);
+
+        Ok(Self((0..capacity).map(|_| AtomicU8::new(0)).collect()))
+    }
+
+    pub(crate) fn read_counter_at(&self, idx: usize) -> u8 {
+        let (element, shift) = read_counter_coordinates(idx);
+        (self.0[element].load(Ordering::Relaxed) >> shift) & Self::COUNTER_MASK
+    }
+
+    // Saturated counters stay saturated, they no longer know how many entries they hold
+    pub(crate) fn increment_at(&self, idx: usize) -> bool {
+        self.update_counter_at(idx, |counter| {
+            (counter != Self::COUNTER_MASK).then(|| counter + 1)
+        })
+    }
+
+    pub(crate) fn decrement_at(&self, idx: usize) -> bool {
+        self.update_counter_at(idx, |counter| {
+            (counter != 0 && counter != Self::COUNTER_MASK).then(|| counter - 1)
+        })
+    }
+
+    pub(crate) fn set_at(&self, idx: usize, value: u8) {
+        self.update_counter_at(idx, |_| Some(value & Self::COUNTER_MASK));
+    }
+
+    // Unlike decrement_at, decaying also lowers saturated counters
+    pub(crate) fn decay_at(&self, idx: usize) -> bool {
+        self.update_counter_at(idx, |counter| counter.checked_sub(1))
+    }
+
+    // Both counters of an element are swapped together, retrying whenever another thread got there first
+    fn update_counter_at(&self, idx: usize, update: impl Fn(u8) -> Option<u8>) -> bool {
+        let (element, shift) = read_counter_coordinates(idx);
+        self.0[element]
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |entry| {
+                update((entry >> shift) & Self::COUNTER_MASK)
+                    .map(|counter| (entry & !(Self::COUNTER_MASK << shift)) | (counter << shift))
+            })
+            .is_ok()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::CounterStorage;
+
+    #[test]
+    fn should_return_err_when_total_counters_is_zero() {
+        assert!(CounterStorage::try_from(0).is_err());
+    }
+
+    #[test]
+    fn should_increment_and_decrement_counter_at_given_index() {
+        let storage = CounterStorage::try_from(4).unwrap();
+
+        assert!(storage.increment_at(1));
+        assert!(storage.increment_at(1));
+        assert_eq!(storage.read_counter_at(0), 0);
+        assert_eq!(storage.read_counter_at(1), 2);
+        assert_eq!(storage.read_counter_at(2), 0);
+
+        assert!(storage.decrement_at(1));
+        assert_eq!(storage.read_counter_at(1), 1);
+    }
+
+    #[test]
+    fn should_not_decrement_empty_counter() {
+        let storage = CounterStorage::try_from(4).unwrap();
+
+        assert!(!storage.decrement_at(3));
+        assert_eq!(storage.read_counter_at(3), 0);
+    }
+
+    #[test]
+    fn should_saturate_counter_at_maximum_value() {
+        let storage = CounterStorage::try_from(2).unwrap();
+
+        for _ in 0..CounterStorage::COUNTER_MASK {
+            assert!(storage.increment_at(0));
+        }
+
+        assert!(!storage.increment_at(0));
+        assert!(!storage.decrement_at(0));
+        assert_eq!(storage.read_counter_at(0), CounterStorage::COUNTER_MASK);
+        assert_eq!(storage.read_counter_at(1), 0);
+    }
+
+    #[test]
+    fn should_set_and_decay_counter_at_given_index() {
+        let storage = CounterStorage::try_from(2).unwrap();
+
+        storage.set_at(1, CounterStorage::COUNTER_MASK);
+        assert_eq!(storage.read_counter_at(0), 0);
+        assert_eq!(storage.read_counter_at(1), CounterStorage::COUNTER_MASK);
+
+        assert!(storage.decay_at(1));
+        assert_eq!(storage.read_counter_at(1), CounterStorage::COUNTER_MASK - 1);
+
+        storage.set_at(1, 1);
+        assert!(storage.decay_at(1));
+        assert!(!storage.decay_at(1));
+        assert_eq!(storage.read_counter_at(1), 0);
+    }
+}
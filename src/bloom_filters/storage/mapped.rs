@@ -0,0 +1,38 @@
+use memmap2::Mmap;
+
+use super::utility::{calculate_storage_capacity_based_on_total_bits, read_bit_coordinates};
+
+// MappedStorage reads the bits straight from a mapped file, they start right after the header
+pub(crate) struct MappedStorage {
+    map: Mmap,
+    offset: usize,
+}
+
+impl MappedStorage {
+    pub(crate) fn try_from(
+        map: Mmap,
+        offset: usize,
+        total_bits: usize,
+    ) -> Result<Self, &'static str> {
+        if total_bits == 0 {
+            return Err("Total bits must be positive");
+        }
+
+        if map.len().checked_sub(offset)
+            != Some(calculate_storage_capacity_based_on_total_bits(total_bits))
+        {
+            return Err("Number of bytes does not match total bits");
+        }
+
+        Ok(Self { map, offset })
+    }
+
+    pub(crate) fn read_bit_at(&self, idx: usize) -> u8 {
+        let (element, bits) = read_bit_coordinates(idx);
+        self.map[self.offset + element] & (1 << bits)
+    }
+
+    pub(crate) fn get_size_in_bytes(&self) -> usize {
+        self.map.len() - self.offset
+    }
+}
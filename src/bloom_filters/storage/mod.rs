@@ -1,36 +1,166 @@
-use std::cell::RefCell;
+use std::sync::atomic::{AtomicU64, Ordering};
 
-use utility::{calculate_storage_capacity_based_on_total_bits, read_bit_coordinates};
+use utility::{
+    calculate_storage_capacity_based_on_total_bits, calculate_storage_words_based_on_total_bits,
+    prefetch, read_word_coordinates, WordType,
+};
 
+use super::SmallestIntType;
+
+pub(super) use counters::CounterStorage;
+pub(super) use mapped::MappedStorage;
+
+mod counters;
+mod mapped;
 mod utility;
 
-pub(super) struct Storage(RefCell<Vec<u8>>);
+// Storage keeps the bits in atomic words, its bytes are the little-endian bytes of every word cut to the total bits
+pub(super) struct Storage {
+    words: Vec<AtomicU64>,
+    size_in_bytes: usize,
+}
 
 impl Storage {
+    const BYTES_PER_WORD: usize = (WordType::BITS / SmallestIntType::BITS) as usize;
+
     pub(super) fn try_from(total_bits: usize) -> Result<Self, &'static str> {
         if total_bits == 0 {
             return Err("Total bits must be positive");
         }
 
-        let capacity = calculate_storage_capacity_based_on_total_bits(total_bits);
+        let capacity = calculate_storage_words_based_on_total_bits(total_bits);
+
+        Ok(Self {
+            words: (0..capacity).map(|_| AtomicU64::new(0)).collect(),
+            size_in_bytes: calculate_storage_capacity_based_on_total_bits(total_bits),
+        })
+    }
+
+    pub(super) fn try_from_bytes(total_bits: usize, bytes: Vec<u8>) -> Result<Self, &'static str> {
+        if total_bits == 0 {
+            return Err("Total bits must be positive");
+        }
+
+        if bytes.len() != calculate_storage_capacity_based_on_total_bits(total_bits) {
+            return Err("Number of bytes does not match total bits");
+        }
+
+        let words = bytes
+            .chunks(Self::BYTES_PER_WORD)
+            .map(|chunk| {
+                let mut word = [0; Self::BYTES_PER_WORD];
+                word[..chunk.len()].copy_from_slice(chunk);
+                AtomicU64::new(WordType::from_le_bytes(word))
+            })
+            .collect();
+
+        Ok(Self {
+            words,
+            size_in_bytes: bytes.len(),
+        })
+    }
+
+    pub(super) fn as_bytes(&self) -> Vec<u8> {
+        self.words
+            .iter()
+            .flat_map(|word| word.load(Ordering::Relaxed).to_le_bytes())
+            .take(self.size_in_bytes)
+            .collect()
+    }
+
+    pub(super) fn count_set_bits(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.load(Ordering::Relaxed).count_ones() as usize)
+            .sum()
+    }
+
+    // Regions are whole bytes, so every region but the last covers the same number of bits
+    pub(super) fn count_set_bits_per_region(
+        &self,
+        num_of_regions: usize,
+        total_bits: usize,
+    ) -> Vec<(usize, usize)> {
+        let bits_per_region =
+            self.size_in_bytes.div_ceil(num_of_regions) * SmallestIntType::BITS as usize;
+
+        (0..total_bits)
+            .step_by(bits_per_region)
+            .map(|first_bit| {
+                let last_bit = (first_bit + bits_per_region).min(total_bits);
+
+                (
+                    self.count_set_bits_between(first_bit, last_bit),
+                    last_bit - first_bit,
+                )
+            })
+            .collect()
+    }
+
+    fn count_set_bits_between(&self, first_bit: usize, last_bit: usize) -> usize {
+        let mut count = 0;
+        let mut idx = first_bit;
+
+        while idx < last_bit {
+            let (element, bits) = read_word_coordinates(idx);
+            let width = (WordType::BITS as usize - bits).min(last_bit - idx);
+            let mask = (WordType::MAX >> (WordType::BITS as usize - width)) << bits;
+
+            count += (self.words[element].load(Ordering::Relaxed) & mask).count_ones() as usize;
+            idx += width;
+        }
+
+        count
+    }
 
-        Ok(Self(RefCell::new(vec![0; capacity])))
+    pub(super) fn iter_set_bits(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(element, word)| {
+            let word = word.load(Ordering::Relaxed);
+
+            (0..WordType::BITS as usize)
+                .filter(move |&bits| word & (1 << bits) != 0)
+                .map(move |bits| element * WordType::BITS as usize + bits)
+        })
+    }
+
+    pub(super) fn union_with(&self, other: &Storage) {
+        self.words
+            .iter()
+            .zip(other.words.iter())
+            .for_each(|(word, other)| {
+                word.fetch_or(other.load(Ordering::Relaxed), Ordering::Relaxed);
+            });
+    }
+
+    pub(super) fn intersect_with(&self, other: &Storage) {
+        self.words
+            .iter()
+            .zip(other.words.iter())
+            .for_each(|(word, other)| {
+                word.fetch_and(other.load(Ordering::Relaxed), Ordering::Relaxed);
+            });
+    }
+
+    pub(super) fn clear(&self) {
+        self.words
+            .iter()
+            .for_each(|word| word.store(0, Ordering::Relaxed));
     }
 
     pub(super) fn write_bit_at(&self, idx: usize) -> bool {
-        let (element, bits) = read_bit_coordinates(idx);
+        let (element, bits) = read_word_coordinates(idx);
         let mask = 1 << bits;
-        let entry = self.0.borrow()[element];
-        if entry & mask != 0 {
-            return false;
-        }
-        self.0.borrow_mut()[element] = entry | mask;
-        true
+        self.words[element].fetch_or(mask, Ordering::Relaxed) & mask == 0
+    }
+
+    pub(super) fn prefetch_bit_at(&self, idx: usize) {
+        let (element, _) = read_word_coordinates(idx);
+        prefetch(&self.words[element]);
     }
 
     pub(super) fn read_bit_at(&self, idx: usize) -> u8 {
-        let (element, bits) = read_bit_coordinates(idx);
-        return self.0.borrow()[element] & (1 << bits);
+        let (element, bits) = read_word_coordinates(idx);
+        ((self.words[element].load(Ordering::Relaxed) >> bits) & 1) as u8
     }
 }
 
@@ -63,6 +193,121 @@ mod test {
         assert!(!is_written);
     }
 
+    #[test]
+    fn should_return_err_when_bytes_do_not_match_total_bits() {
+        let storage = super::Storage::try_from_bytes(16, vec![0; 1]);
+        assert!(storage.is_err());
+    }
+
+    #[test]
+    fn should_restore_storage_from_bytes() {
+        let storage = super::Storage::try_from(16).unwrap();
+        storage.write_bit_at(9);
+
+        let restored = super::Storage::try_from_bytes(16, storage.as_bytes()).unwrap();
+        assert_ne!(restored.read_bit_at(9), 0);
+        assert_eq!(restored.read_bit_at(8), 0);
+    }
+
+    #[test]
+    fn should_clear_every_bit() {
+        let storage = super::Storage::try_from(16).unwrap();
+        storage.write_bit_at(3);
+        storage.write_bit_at(12);
+
+        storage.clear();
+
+        assert_eq!(storage.count_set_bits(), 0);
+    }
+
+    #[test]
+    fn should_count_set_bits() {
+        let storage = super::Storage::try_from(24).unwrap();
+        assert_eq!(storage.count_set_bits(), 0);
+
+        [0, 7, 8, 23, 23].into_iter().for_each(|idx| {
+            storage.write_bit_at(idx);
+        });
+        assert_eq!(storage.count_set_bits(), 4);
+    }
+
+    #[test]
+    fn should_count_set_bits_per_region() {
+        let storage = super::Storage::try_from(20).unwrap();
+
+        [0, 1, 9, 19].into_iter().for_each(|idx| {
+            storage.write_bit_at(idx);
+        });
+
+        assert_eq!(
+            storage.count_set_bits_per_region(3, 20),
+            vec![(2, 8), (1, 8), (1, 4)]
+        );
+        assert_eq!(storage.count_set_bits_per_region(1, 20), vec![(4, 20)]);
+    }
+
+    #[test]
+    fn should_iterate_set_bits_in_order() {
+        let storage = super::Storage::try_from(24).unwrap();
+
+        [23, 0, 9, 8].into_iter().for_each(|idx| {
+            storage.write_bit_at(idx);
+        });
+
+        assert_eq!(
+            storage.iter_set_bits().collect::<Vec<_>>(),
+            vec![0, 8, 9, 23]
+        );
+    }
+
+    #[test]
+    fn should_combine_bits_of_both_storages() {
+        let (left, right) = (
+            super::Storage::try_from(16).unwrap(),
+            super::Storage::try_from(16).unwrap(),
+        );
+        left.write_bit_at(1);
+        left.write_bit_at(9);
+        right.write_bit_at(9);
+        right.write_bit_at(12);
+
+        let union = super::Storage::try_from_bytes(16, left.as_bytes()).unwrap();
+        union.union_with(&right);
+        assert_eq!(union.as_bytes(), vec![0b0000_0010, 0b0001_0010]);
+
+        left.intersect_with(&right);
+        assert_eq!(left.as_bytes(), vec![0b0000_0000, 0b0000_0010]);
+    }
+
+    #[test]
+    fn should_keep_byte_order_of_bits_across_words() {
+        let storage = super::Storage::try_from(80).unwrap();
+
+        [0, 9, 63, 64, 79].into_iter().for_each(|idx| {
+            storage.write_bit_at(idx);
+        });
+
+        assert_eq!(
+            storage.as_bytes(),
+            vec![
+                0b0000_0001,
+                0b0000_0010,
+                0,
+                0,
+                0,
+                0,
+                0,
+                0b1000_0000,
+                0b0000_0001,
+                0b1000_0000
+            ]
+        );
+        assert_eq!(
+            storage.count_set_bits_per_region(2, 80),
+            vec![(2, 40), (3, 40)]
+        );
+    }
+
     #[test]
     fn should_read_bit_at_given_index() {
         let storage = super::Storage::try_from(8).unwrap();
@@ -1,4 +1,4 @@
-use crate::bloom_filters::{SmallestIntType, SupportedFloatingPointType};
+use crate::bloom_filters::SmallestIntType;
 
 pub(super) fn read_bit_coordinates(idx: usize) -> (usize, usize) {
     let element = idx / (SmallestIntType::BITS as usize);
@@ -7,16 +7,61 @@ pub(super) fn read_bit_coordinates(idx: usize) -> (usize, usize) {
 }
 
 pub(super) fn calculate_storage_capacity_based_on_total_bits(total_bits: usize) -> usize {
-    ((total_bits as SupportedFloatingPointType)
-        / (SmallestIntType::BITS as SupportedFloatingPointType))
-        .ceil() as usize
+    total_bits.div_ceil(SmallestIntType::BITS as usize)
+}
+
+pub(super) type WordType = u64;
+
+pub(super) fn read_word_coordinates(idx: usize) -> (usize, usize) {
+    let element = idx / (WordType::BITS as usize);
+    let offset = idx % (WordType::BITS as usize);
+    (element, offset)
+}
+
+pub(super) fn calculate_storage_words_based_on_total_bits(total_bits: usize) -> usize {
+    total_bits.div_ceil(WordType::BITS as usize)
+}
+
+// Hints the CPU to pull the cache line holding the value before it is read or written
+pub(super) fn prefetch<T>(value: &T) {
+    // SAFETY: the pointer comes from a live reference, and a prefetch never dereferences it anyway, it only
+    // hints the cache. _mm_prefetch needs SSE, which is part of the x86_64 baseline every such target has
+    #[cfg(target_arch = "x86_64")]
+    unsafe {
+        use std::arch::x86_64::{_mm_prefetch, _MM_HINT_T0};
+        _mm_prefetch::<_MM_HINT_T0>(value as *const T as *const i8);
+    }
+
+    #[cfg(not(target_arch = "x86_64"))]
+    let _ = value;
+}
+
+pub(super) const BITS_PER_COUNTER: usize = 4;
+
+pub(super) fn read_counter_coordinates(idx: usize) -> (usize, usize) {
+    let counters_per_element = SmallestIntType::BITS as usize / BITS_PER_COUNTER;
+    let element = idx / counters_per_element;
+    let shift = (idx % counters_per_element) * BITS_PER_COUNTER;
+    (element, shift)
+}
+
+pub(super) fn calculate_storage_capacity_based_on_total_counters(total_counters: usize) -> usize {
+    total_counters.div_ceil(SmallestIntType::BITS as usize / BITS_PER_COUNTER)
 }
 
 #[cfg(test)]
 mod test {
     #[test]
     fn should_provide_correct_capacity() {
-        let test_cases = vec![(1, 1), (8, 1), (9, 2), (16, 2), (17, 3), (24, 3)];
+        let test_cases = vec![
+            (1, 1),
+            (8, 1),
+            (9, 2),
+            (16, 2),
+            (17, 3),
+            (24, 3),
+            ((1 << 33) + 1, (1 << 30) + 1),
+        ];
 
         for (total_bits, expected_capacity) in test_cases {
             let capacity = super::calculate_storage_capacity_based_on_total_bits(total_bits);
@@ -36,6 +81,7 @@ mod test {
             (16, (2, 0)),
             (17, (2, 1)),
             (23, (2, 7)),
+            ((1 << 35) + 3, (1 << 32, 3)),
         ];
 
         for (idx, expected_coordinates) in test_cases {
@@ -43,4 +89,49 @@ mod test {
             assert_eq!(coordinates, expected_coordinates);
         }
     }
+
+    #[test]
+    fn should_provide_correct_word_capacity_and_coordinates() {
+        let test_cases = vec![
+            (1, 1, (0, 1)),
+            (64, 1, (1, 0)),
+            (65, 2, (1, 1)),
+            (130, 3, (2, 2)),
+        ];
+
+        for (total_bits, expected_capacity, expected_coordinates) in test_cases {
+            let capacity = super::calculate_storage_words_based_on_total_bits(total_bits);
+            assert_eq!(capacity, expected_capacity);
+
+            let coordinates = super::read_word_coordinates(total_bits);
+            assert_eq!(coordinates, expected_coordinates);
+        }
+    }
+
+    #[test]
+    fn should_provide_correct_counter_capacity() {
+        let test_cases = vec![(1, 1), (2, 1), (3, 2), (4, 2), (5, 3)];
+
+        for (total_counters, expected_capacity) in test_cases {
+            let capacity =
+                super::calculate_storage_capacity_based_on_total_counters(total_counters);
+            assert_eq!(capacity, expected_capacity);
+        }
+    }
+
+    #[test]
+    fn should_provide_correct_counter_coordinates() {
+        let test_cases = vec![
+            (0, (0, 0)),
+            (1, (0, 4)),
+            (2, (1, 0)),
+            (3, (1, 4)),
+            (8, (4, 0)),
+        ];
+
+        for (idx, expected_coordinates) in test_cases {
+            let coordinates = super::read_counter_coordinates(idx);
+            assert_eq!(coordinates, expected_coordinates);
+        }
+    }
 }
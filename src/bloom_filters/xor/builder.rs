@@ -0,0 +1,46 @@
+use std::{hash::Hash, marker::PhantomData};
+
+use super::XorFilter;
+use crate::bloom_filters::{hashing::strategy::Hashing, BloomFilterError};
+
+pub struct Builder<T: ?Sized = str> {
+    fingerprint_bits: Option<usize>,
+    strategy: Option<Box<dyn Hashing>>,
+    entry: PhantomData<fn(&T)>,
+}
+
+impl<T: ?Sized> Default for Builder<T> {
+    fn default() -> Self {
+        Self {
+            fingerprint_bits: None,
+            strategy: None,
+            entry: PhantomData,
+        }
+    }
+}
+
+impl<T: Hash + ?Sized> Builder<T> {
+    pub fn build_from<'a, I>(self, entries: I) -> Result<XorFilter<T>, BloomFilterError>
+    where
+        I: IntoIterator<Item = &'a T>,
+        T: 'a,
+    {
+        XorFilter::try_from(entries, self.fingerprint_bits.unwrap_or(8), self.strategy)
+    }
+
+    pub fn with_fingerprint_bits(self, fingerprint_bits: usize) -> Self {
+        Self {
+            fingerprint_bits: Some(fingerprint_bits),
+            strategy: self.strategy,
+            entry: PhantomData,
+        }
+    }
+
+    pub fn with_strategy(self, strategy: Box<dyn Hashing>) -> Self {
+        Self {
+            fingerprint_bits: self.fingerprint_bits,
+            strategy: Some(strategy),
+            entry: PhantomData,
+        }
+    }
+}
@@ -0,0 +1,67 @@
+pub(super) enum Fingerprints {
+    Eight(Vec<u8>),
+    Sixteen(Vec<u16>),
+}
+
+impl Fingerprints {
+    pub(super) fn try_from(
+        fingerprint_bits: usize,
+        values: Vec<u16>,
+    ) -> Result<Self, &'static str> {
+        match fingerprint_bits {
+            8 => Ok(Self::Eight(
+                values.into_iter().map(|value| value as u8).collect(),
+            )),
+            16 => Ok(Self::Sixteen(values)),
+            _ => Err("Fingerprints must have 8 or 16 bits"),
+        }
+    }
+
+    pub(super) fn try_from_bytes(
+        fingerprint_bits: usize,
+        bytes: Vec<u8>,
+    ) -> Result<Self, &'static str> {
+        match fingerprint_bits {
+            8 => Ok(Self::Eight(bytes)),
+            16 if bytes.len().is_multiple_of(2) => Ok(Self::Sixteen(
+                bytes
+                    .chunks_exact(2)
+                    .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+                    .collect(),
+            )),
+            16 => Err("Number of bytes does not match fingerprint bits"),
+            _ => Err("Fingerprints must have 8 or 16 bits"),
+        }
+    }
+
+    pub(super) fn as_bytes(&self) -> Vec<u8> {
+        match self {
+            Self::Eight(values) => values.clone(),
+            Self::Sixteen(values) => values
+                .iter()
+                .flat_map(|value| value.to_le_bytes())
+                .collect(),
+        }
+    }
+
+    pub(super) fn read(&self, idx: usize) -> u16 {
+        match self {
+            Self::Eight(values) => values[idx] as u16,
+            Self::Sixteen(values) => values[idx],
+        }
+    }
+
+    pub(super) fn get_fingerprint_bits(&self) -> usize {
+        match self {
+            Self::Eight(_) => 8,
+            Self::Sixteen(_) => 16,
+        }
+    }
+
+    pub(super) fn get_size_in_bytes(&self) -> usize {
+        match self {
+            Self::Eight(values) => values.len(),
+            Self::Sixteen(values) => values.len() * 2,
+        }
+    }
+}
@@ -0,0 +1,450 @@
+use std::{
+    fs::File,
+    hash::Hash,
+    io::{BufReader, BufWriter, Read, Write},
+    marker::PhantomData,
+};
+
+use builder::Builder;
+use fingerprints::Fingerprints;
+
+use super::{
+    configurations::ConfigError,
+    hashing::{
+        strategy::{self, remix, Hashing},
+        HashManager,
+    },
+    persistence::XorHeader,
+    BloomFilterError, SupportedFloatingPointType,
+};
+
+mod builder;
+mod fingerprints;
+
+// XorFilter follows Graf & Lemire: every entry maps to one slot in each of three blocks, and the
+// fingerprints are assigned once so that the three slots of an entry XOR to its own fingerprint
+pub struct XorFilter<T: ?Sized = str> {
+    manager: HashManager,
+    construction_seed: u64,
+    block_length: usize,
+    fingerprints: Fingerprints,
+    unique_entry_count: usize,
+    entry: PhantomData<fn(&T)>,
+}
+
+impl<T: Hash + ?Sized> XorFilter<T> {
+    const NUM_OF_BLOCKS: usize = 3;
+    const MAX_CONSTRUCTION_ATTEMPTS: u64 = 100;
+
+    pub fn builder() -> Builder<T> {
+        Builder::default()
+    }
+
+    // Every block holds a third of 1.23 slots per entry plus a few spare ones, which lets peeling succeed
+    fn calculate_block_length(num_of_entries: usize) -> usize {
+        (32 + (1.23 * num_of_entries as SupportedFloatingPointType).ceil() as usize)
+            / Self::NUM_OF_BLOCKS
+    }
+
+    fn try_from<'a, I>(
+        entries: I,
+        fingerprint_bits: usize,
+        strategy: Option<Box<dyn Hashing>>,
+    ) -> Result<Self, BloomFilterError>
+    where
+        I: IntoIterator<Item = &'a T>,
+        T: 'a,
+    {
+        if fingerprint_bits != 8 && fingerprint_bits != 16 {
+            return Err(BloomFilterError::Configuration(ConfigError::Validation(
+                String::from("Fingerprints must have 8 or 16 bits"),
+            )));
+        }
+
+        let mut manager = manage_hashing(strategy)?;
+
+        // Peeling can never separate two equal keys, so duplicates are dropped up front
+        let mut keys: Vec<u64> = entries
+            .into_iter()
+            .map(|entry| manager.hash_base(entry).0)
+            .collect();
+        keys.sort_unstable();
+        keys.dedup();
+
+        let block_length = Self::calculate_block_length(keys.len());
+
+        for construction_seed in 0..Self::MAX_CONSTRUCTION_ATTEMPTS {
+            let filter = Self {
+                manager,
+                construction_seed,
+                block_length,
+                fingerprints: Fingerprints::Sixteen(vec![]),
+                unique_entry_count: keys.len(),
+                entry: PhantomData,
+            };
+
+            match filter.assign_fingerprints(&keys, fingerprint_bits) {
+                Ok(filter) => return Ok(filter),
+                Err(filter) => manager = filter.manager,
+            }
+        }
+
+        Err(BloomFilterError::Hashing(String::from(
+            "Cannot find a seed mapping every entry to its own slot",
+        )))
+    }
+
+    pub fn load(path: &str) -> Result<Self, BloomFilterError> {
+        let file = File::open(path)
+            .map_err(|_| BloomFilterError::Persistence(format!("Cannot open file at {}", path)))?;
+
+        Self::read_from(&mut BufReader::new(file))
+    }
+
+    pub fn read_from<R: Read>(reader: &mut R) -> Result<Self, BloomFilterError> {
+        let header = XorHeader::read_from(reader)
+            .map_err(String::from)
+            .map_err(BloomFilterError::Persistence)?;
+
+        let strategy = strategy::from_identifier(header.strategy_identifier, header.seed)
+            .ok_or_else(|| {
+                BloomFilterError::Persistence(format!(
+                    "Unknown hashing strategy {}",
+                    header.strategy_identifier
+                ))
+            })?;
+
+        let fingerprint_bits = header.fingerprint_bits as usize;
+        let block_length = usize::try_from(header.block_length).map_err(|_| {
+            BloomFilterError::Persistence(String::from("Block length does not fit this platform"))
+        })?;
+        let unique_entry_count = usize::try_from(header.unique_entry_count).map_err(|_| {
+            BloomFilterError::Persistence(String::from("Entry count does not fit this platform"))
+        })?;
+
+        if fingerprint_bits != 8 && fingerprint_bits != 16 {
+            return Err(BloomFilterError::Persistence(String::from(
+                "Fingerprints must have 8 or 16 bits",
+            )));
+        }
+
+        if block_length != Self::calculate_block_length(unique_entry_count) {
+            return Err(BloomFilterError::Persistence(String::from(
+                "Block length does not match the number of entries",
+            )));
+        }
+
+        let size_in_bytes = block_length
+            .checked_mul(Self::NUM_OF_BLOCKS * fingerprint_bits / 8)
+            .ok_or_else(|| BloomFilterError::Persistence(String::from("Storage is too large")))?;
+
+        // The buffer only grows with the bytes the stream actually holds, so a forged header cannot reserve memory
+        let mut bytes = vec![];
+        reader
+            .take(size_in_bytes as u64)
+            .read_to_end(&mut bytes)
+            .map_err(|_| BloomFilterError::Persistence(String::from("Cannot read the storage")))?;
+
+        if bytes.len() != size_in_bytes {
+            return Err(BloomFilterError::Persistence(String::from(
+                "Storage is truncated",
+            )));
+        }
+
+        let fingerprints = Fingerprints::try_from_bytes(fingerprint_bits, bytes)
+            .map_err(String::from)
+            .map_err(BloomFilterError::Storage)?;
+
+        Ok(Self {
+            manager: manage_hashing(Some(strategy))?,
+            construction_seed: header.construction_seed,
+            block_length,
+            fingerprints,
+            unique_entry_count,
+            entry: PhantomData,
+        })
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), BloomFilterError> {
+        let file = File::create(path).map_err(|_| {
+            BloomFilterError::Persistence(format!("Cannot create file at {}", path))
+        })?;
+
+        let mut writer = BufWriter::new(file);
+
+        self.write_to(&mut writer)?;
+
+        writer
+            .flush()
+            .map_err(|_| BloomFilterError::Persistence(format!("Cannot write to file at {}", path)))
+    }
+
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> Result<(), BloomFilterError> {
+        self.manager
+            .try_restore_strategy()
+            .map_err(String::from)
+            .map_err(BloomFilterError::Persistence)?;
+
+        let header = XorHeader {
+            fingerprint_bits: self.fingerprints.get_fingerprint_bits() as u8,
+            seed: self.manager.get_seed(),
+            strategy_identifier: self.manager.get_strategy_identifier(),
+            construction_seed: self.construction_seed,
+            block_length: self.block_length as u64,
+            unique_entry_count: self.unique_entry_count as u64,
+        };
+
+        header
+            .write_to(writer)
+            .map_err(String::from)
+            .map_err(BloomFilterError::Persistence)?;
+
+        writer
+            .write_all(&self.fingerprints.as_bytes())
+            .map_err(|_| BloomFilterError::Persistence(String::from("Cannot write the storage")))
+    }
+
+    pub fn contains(&self, entry: &T) -> bool {
+        let hash = self.mix(self.manager.hash_base(entry).0);
+
+        let fingerprint = self.locate(hash).into_iter().fold(0, |fingerprint, idx| {
+            fingerprint ^ self.fingerprints.read(idx)
+        });
+
+        fingerprint == self.fingerprint_of(hash, self.fingerprints.get_fingerprint_bits())
+    }
+
+    pub fn get_unique_entry_count(&self) -> usize {
+        self.unique_entry_count
+    }
+
+    pub fn get_size_in_bytes(&self) -> usize {
+        self.fingerprints.get_size_in_bytes()
+    }
+
+    pub fn expected_false_positive_rate(&self) -> SupportedFloatingPointType {
+        1.0 / (1u64 << self.fingerprints.get_fingerprint_bits()) as SupportedFloatingPointType
+    }
+
+    // Gives the filter back on failure so the next attempt can reuse its hash manager
+    fn assign_fingerprints(mut self, keys: &[u64], fingerprint_bits: usize) -> Result<Self, Self> {
+        let capacity = Self::NUM_OF_BLOCKS * self.block_length;
+        let mut counts = vec![0u32; capacity];
+        let mut xored_keys = vec![0u64; capacity];
+
+        keys.iter().for_each(|&key| {
+            for idx in self.locate(self.mix(key)) {
+                counts[idx] += 1;
+                xored_keys[idx] ^= key;
+            }
+        });
+
+        let mut queue: Vec<usize> = (0..capacity).filter(|&idx| counts[idx] == 1).collect();
+        let mut peeled = Vec::with_capacity(keys.len());
+
+        // A slot hit by a single key determines that key, removing it may free other slots in turn
+        while let Some(slot) = queue.pop() {
+            if counts[slot] != 1 {
+                continue;
+            }
+
+            let key = xored_keys[slot];
+            peeled.push((key, slot));
+
+            for idx in self.locate(self.mix(key)) {
+                counts[idx] -= 1;
+                xored_keys[idx] ^= key;
+
+                if counts[idx] == 1 {
+                    queue.push(idx);
+                }
+            }
+        }
+
+        if peeled.len() != keys.len() {
+            return Err(self);
+        }
+
+        let mut fingerprints = vec![0u16; capacity];
+
+        peeled.into_iter().rev().for_each(|(key, slot)| {
+            let hash = self.mix(key);
+            let others = self
+                .locate(hash)
+                .into_iter()
+                .fold(0, |fingerprint, idx| fingerprint ^ fingerprints[idx]);
+
+            fingerprints[slot] = self.fingerprint_of(hash, fingerprint_bits) ^ others;
+        });
+
+        match Fingerprints::try_from(fingerprint_bits, fingerprints) {
+            Ok(fingerprints) => {
+                self.fingerprints = fingerprints;
+                Ok(self)
+            }
+            Err(_) => Err(self),
+        }
+    }
+
+    fn mix(&self, key: u64) -> u64 {
+        remix(key.wrapping_add(self.construction_seed))
+    }
+
+    fn locate(&self, hash: u64) -> [usize; 3] {
+        [0, 1, 2].map(|block| {
+            let block_hash = hash.rotate_left(21 * block as u32) as u32;
+            let offset = ((block_hash as u64 * self.block_length as u64) >> 32) as usize;
+            block * self.block_length + offset
+        })
+    }
+
+    fn fingerprint_of(&self, hash: u64, fingerprint_bits: usize) -> u16 {
+        ((hash ^ (hash >> 32)) & ((1u64 << fingerprint_bits) - 1)) as u16
+    }
+}
+
+fn manage_hashing(strategy: Option<Box<dyn Hashing>>) -> Result<HashManager, BloomFilterError> {
+    let mut manager_builder = HashManager::builder()
+        .with_num_of_hash_funcs(1)
+        .with_total_bits(1);
+
+    if let Some(strategy) = strategy {
+        manager_builder = manager_builder.use_strategy(strategy);
+    }
+
+    manager_builder
+        .build()
+        .map_err(String::from)
+        .map_err(BloomFilterError::Hashing)
+}
+
+#[cfg(test)]
+mod test {
+    use super::XorFilter;
+    use crate::bloom_filters::{
+        configurations::ConfigError, persistence::XorHeader, BloomFilterError, Xxh3HashingStrategy,
+    };
+
+    fn build_words(range: std::ops::Range<u64>) -> Vec<String> {
+        range.map(|idx| format!("word-{}", idx)).collect()
+    }
+
+    #[test]
+    fn should_contain_every_entry_it_was_built_from() {
+        for fingerprint_bits in [8, 16] {
+            let words = build_words(0..10_000);
+
+            let filter: XorFilter = XorFilter::builder()
+                .with_fingerprint_bits(fingerprint_bits)
+                .build_from(words.iter().map(String::as_str))
+                .unwrap();
+
+            assert!(words.iter().all(|word| filter.contains(word)));
+            assert_eq!(filter.get_unique_entry_count(), 10_000);
+        }
+    }
+
+    #[test]
+    fn should_keep_false_positive_rate_close_to_fingerprint_size() {
+        let words = build_words(0..10_000);
+        let unseen = build_words(10_000..1_010_000);
+
+        for fingerprint_bits in [8, 16] {
+            let filter: XorFilter = XorFilter::builder()
+                .with_fingerprint_bits(fingerprint_bits)
+                .build_from(words.iter().map(String::as_str))
+                .unwrap();
+
+            let false_positives = unseen.iter().filter(|word| filter.contains(word)).count();
+            let rate = false_positives as f64 / unseen.len() as f64;
+
+            assert!(rate <= filter.expected_false_positive_rate() * 2.0);
+            assert!(filter.get_size_in_bytes() <= 10_000 * fingerprint_bits / 8 * 125 / 100);
+        }
+    }
+
+    #[test]
+    fn should_ignore_duplicated_entries() {
+        let filter: XorFilter = XorFilter::builder()
+            .build_from(["hello", "world", "hello"])
+            .unwrap();
+
+        assert!(filter.contains("hello"));
+        assert!(filter.contains("world"));
+        assert_eq!(filter.get_unique_entry_count(), 2);
+    }
+
+    #[test]
+    fn should_build_from_no_entries() {
+        let filter: XorFilter = XorFilter::builder().build_from([]).unwrap();
+
+        assert_eq!(filter.get_unique_entry_count(), 0);
+    }
+
+    #[test]
+    fn should_return_err_when_fingerprint_bits_are_unsupported() {
+        for fingerprint_bits in [0, 4, 12, 32] {
+            let maybe_filter = XorFilter::<str>::builder()
+                .with_fingerprint_bits(fingerprint_bits)
+                .build_from(["hello"]);
+
+            assert!(matches!(
+                maybe_filter,
+                Err(BloomFilterError::Configuration(ConfigError::Validation(_)))
+            ));
+        }
+    }
+
+    #[test]
+    fn should_contain_entries_after_round_trip() {
+        let words = build_words(0..1_000);
+
+        let filter: XorFilter = XorFilter::builder()
+            .with_fingerprint_bits(16)
+            .with_strategy(Box::new(Xxh3HashingStrategy::with_seed(7)))
+            .build_from(words.iter().map(String::as_str))
+            .unwrap();
+
+        let mut bytes = vec![];
+        filter.write_to(&mut bytes).unwrap();
+        let restored = XorFilter::<str>::read_from(&mut bytes.as_slice()).unwrap();
+
+        assert!(words.iter().all(|word| restored.contains(word)));
+        assert_eq!(restored.get_unique_entry_count(), 1_000);
+        assert_eq!(
+            restored.fingerprints.as_bytes(),
+            filter.fingerprints.as_bytes()
+        );
+
+        bytes.pop();
+        assert!(XorFilter::<str>::read_from(&mut bytes.as_slice()).is_err());
+    }
+
+    #[test]
+    fn should_return_err_when_header_is_forged() {
+        let filter: XorFilter = XorFilter::builder().build_from(["hello"]).unwrap();
+
+        let mut bytes = vec![];
+        filter.write_to(&mut bytes).unwrap();
+
+        let mut zero_blocks = bytes.clone();
+        zero_blocks[24..32].copy_from_slice(&0u64.to_le_bytes());
+
+        let num_of_entries = 1usize << 45;
+        let mut huge_blocks = bytes[..XorHeader::SIZE].to_vec();
+        huge_blocks[24..32].copy_from_slice(
+            &(XorFilter::<str>::calculate_block_length(num_of_entries) as u64).to_le_bytes(),
+        );
+        huge_blocks[32..40].copy_from_slice(&(num_of_entries as u64).to_le_bytes());
+
+        let mut overflowing_blocks = bytes[..XorHeader::SIZE].to_vec();
+        overflowing_blocks[24..32].copy_from_slice(&u64::MAX.to_le_bytes());
+
+        for forged in [zero_blocks, huge_blocks, overflowing_blocks] {
+            assert!(matches!(
+                XorFilter::<str>::read_from(&mut forged.as_slice()),
+                Err(BloomFilterError::Persistence(_))
+            ));
+        }
+    }
+}
@@ -16,7 +16,7 @@ fn use_password_detector() {
     let (buffer, storage) = init_buffer_and_storage();
 
     let detector = PasswordDetector::builder()
-        .with_buffer(buffer)
+        .with_buffer(Box::new(buffer))
         .with_database(storage)
         .build()
         .unwrap();
@@ -35,6 +35,7 @@ fn use_password_detector() {
         match detector.verify(word) {
             DetectError::Initialize(value) => println!("{}", value),
             DetectError::Storage(err) => println!("{:?}", err),
+            DetectError::Buffer(err) => println!("{:?}", err),
             DetectError::Dismiss => {
                 println!("{} is a common password. Please try with another one", word)
             }
@@ -47,7 +48,7 @@ fn use_spell_checker() {
     let (buffer, storage) = init_buffer_and_storage();
 
     let spelling_checker = SpellChecker::builder()
-        .with_buffer(buffer)
+        .with_buffer(Box::new(buffer))
         .with_database(storage)
         .build()
         .unwrap();
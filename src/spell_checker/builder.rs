@@ -1,42 +1,80 @@
-use crate::bloom_filters::BloomFilter;
+use crate::bloom_filters::{self, ApproximateMembership, XorFilter};
 
 use super::{SpellChecker, SpellCheckerError, StorageService};
 
 #[derive(Default)]
 pub struct Builder {
-    buffer: Option<BloomFilter>,
+    buffer: Option<Box<dyn ApproximateMembership>>,
     database: Option<Box<dyn StorageService>>,
+    snapshot: Option<String>,
+    fingerprint_bits: Option<usize>,
 }
 
 impl Builder {
     pub fn build(self) -> Result<SpellChecker, SpellCheckerError> {
-        if self.buffer.is_none() {
+        let num_of_buffer_sources = [
+            self.buffer.is_some(),
+            self.snapshot.is_some(),
+            self.fingerprint_bits.is_some(),
+        ]
+        .into_iter()
+        .filter(|&is_set| is_set)
+        .count();
+
+        if num_of_buffer_sources == 0 {
             return Err(SpellCheckerError::Initialization(String::from(
                 "Buffer has not been initialized",
             )));
         }
 
+        if num_of_buffer_sources > 1 {
+            return Err(SpellCheckerError::Initialization(String::from(
+                "Buffer can only come from one of a buffer, a snapshot or a static buffer",
+            )));
+        }
+
         if self.database.is_none() {
             return Err(SpellCheckerError::Initialization(String::from(
                 "Database has not been initialized",
             )));
         }
 
-        let (buffer, database) = (self.buffer.unwrap(), self.database.unwrap());
+        let database = self.database.unwrap();
+
+        if let Some(snapshot) = self.snapshot {
+            let buffer = bloom_filters::load(&snapshot).map_err(SpellCheckerError::Buffer)?;
+
+            return Ok(SpellChecker::from(buffer, database));
+        }
 
         let words = database
             .retrieve_all()
             .map_err(SpellCheckerError::Storage)?;
 
-        words.into_iter().for_each(|entry| buffer.insert(entry));
+        if let Some(fingerprint_bits) = self.fingerprint_bits {
+            let buffer: XorFilter = XorFilter::builder()
+                .with_fingerprint_bits(fingerprint_bits)
+                .build_from(words.iter().map(String::as_str))
+                .map_err(SpellCheckerError::Buffer)?;
+
+            return Ok(SpellChecker::from(Box::new(buffer), database));
+        }
+
+        let buffer = self.buffer.unwrap();
+
+        buffer
+            .insert_all(&words.iter().map(String::as_str).collect::<Vec<_>>())
+            .map_err(SpellCheckerError::Buffer)?;
 
         Ok(SpellChecker::from(buffer, database))
     }
 
-    pub fn with_buffer(self, buffer: BloomFilter) -> Self {
+    pub fn with_buffer(self, buffer: Box<dyn ApproximateMembership>) -> Self {
         Self {
             buffer: Some(buffer),
             database: self.database,
+            snapshot: self.snapshot,
+            fingerprint_bits: self.fingerprint_bits,
         }
     }
 
@@ -44,6 +82,26 @@ impl Builder {
         Self {
             buffer: self.buffer,
             database: Some(database),
+            snapshot: self.snapshot,
+            fingerprint_bits: self.fingerprint_bits,
+        }
+    }
+
+    pub fn with_snapshot(self, path: &str) -> Self {
+        Self {
+            buffer: self.buffer,
+            database: self.database,
+            snapshot: Some(path.to_string()),
+            fingerprint_bits: self.fingerprint_bits,
+        }
+    }
+
+    pub fn with_static_buffer(self, fingerprint_bits: usize) -> Self {
+        Self {
+            buffer: self.buffer,
+            database: self.database,
+            snapshot: self.snapshot,
+            fingerprint_bits: Some(fingerprint_bits),
         }
     }
 }
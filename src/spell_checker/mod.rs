@@ -2,7 +2,7 @@ use builder::Builder;
 
 pub use storage_service::{local_storage::LocalStorage, StorageService, StorageServiceError};
 
-use crate::bloom_filters::BloomFilter;
+use crate::bloom_filters::{ApproximateMembership, BloomFilterError};
 
 mod storage_service;
 
@@ -12,10 +12,11 @@ mod builder;
 pub enum SpellCheckerError {
     Storage(StorageServiceError),
     Initialization(String),
+    Buffer(BloomFilterError),
 }
 
 pub struct SpellChecker {
-    buffer: BloomFilter,
+    buffer: Box<dyn ApproximateMembership>,
     database: Box<dyn StorageService>,
 }
 
@@ -24,7 +25,7 @@ impl SpellChecker {
         Builder::default()
     }
 
-    fn from(buffer: BloomFilter, database: Box<dyn StorageService>) -> Self {
+    fn from(buffer: Box<dyn ApproximateMembership>, database: Box<dyn StorageService>) -> Self {
         Self { buffer, database }
     }
 
@@ -38,6 +39,10 @@ impl SpellChecker {
         Ok(false)
     }
 
+    pub fn save_buffer(&self, path: &str) -> Result<(), SpellCheckerError> {
+        self.buffer.save(path).map_err(SpellCheckerError::Buffer)
+    }
+
     pub fn update_knowledge(&self, entries: Vec<String>) -> Result<(), SpellCheckerError> {
         self.database
             .save_bulk(
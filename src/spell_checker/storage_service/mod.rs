@@ -8,7 +8,7 @@ pub enum StorageServiceError {
     NetworkIssue(String),
 }
 
-pub trait StorageService {
+pub trait StorageService: Send + Sync {
     fn contains(&self, entry: &str) -> Result<bool, StorageServiceError>;
     fn save(&self, entry: String) -> Result<(), StorageServiceError>;
     fn save_bulk(&self, entries: Vec<String>) -> Result<(), StorageServiceError>;
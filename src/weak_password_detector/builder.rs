@@ -1,21 +1,55 @@
-use crate::{bloom_filters::BloomFilter, spell_checker::StorageService};
+use crate::{
+    bloom_filters::{self, ApproximateMembership, MappedBloomFilter},
+    spell_checker::StorageService,
+};
 
 use super::{DetectError, PasswordDetector};
 
 #[derive(Default)]
 pub struct Builder {
-    buffer: Option<BloomFilter>,
+    buffer: Option<Box<dyn ApproximateMembership>>,
     database: Option<Box<dyn StorageService>>,
+    snapshot: Option<String>,
+    mapped_snapshot: Option<String>,
 }
 
 impl Builder {
     pub fn build(self) -> Result<PasswordDetector, DetectError> {
+        let num_of_buffer_sources = [
+            self.buffer.is_some(),
+            self.snapshot.is_some(),
+            self.mapped_snapshot.is_some(),
+        ]
+        .into_iter()
+        .filter(|&is_set| is_set)
+        .count();
+
+        if num_of_buffer_sources > 1 {
+            return Err(DetectError::Initialize(String::from(
+                "Buffer can only come from one of a buffer, a snapshot or a mapped snapshot",
+            )));
+        }
+
         if self.database.is_none() {
             return Err(DetectError::Initialize(String::from(
                 "Storage has not been initialized",
             )));
         }
 
+        if let Some(snapshot) = self.mapped_snapshot {
+            return Ok(PasswordDetector {
+                buffer: Box::new(MappedBloomFilter::open(&snapshot).map_err(DetectError::Buffer)?),
+                database: self.database.unwrap(),
+            });
+        }
+
+        if let Some(snapshot) = self.snapshot {
+            return Ok(PasswordDetector {
+                buffer: bloom_filters::load(&snapshot).map_err(DetectError::Buffer)?,
+                database: self.database.unwrap(),
+            });
+        }
+
         if self.buffer.is_none() {
             return Err(DetectError::Initialize(String::from(
                 "Buffer has not been initialized",
@@ -28,10 +62,12 @@ impl Builder {
         })
     }
 
-    pub fn with_buffer(self, buffer: BloomFilter) -> Self {
+    pub fn with_buffer(self, buffer: Box<dyn ApproximateMembership>) -> Self {
         Self {
             buffer: Some(buffer),
             database: self.database,
+            snapshot: self.snapshot,
+            mapped_snapshot: self.mapped_snapshot,
         }
     }
 
@@ -39,6 +75,26 @@ impl Builder {
         Self {
             buffer: self.buffer,
             database: Some(database),
+            snapshot: self.snapshot,
+            mapped_snapshot: self.mapped_snapshot,
+        }
+    }
+
+    pub fn with_snapshot(self, path: &str) -> Self {
+        Self {
+            buffer: self.buffer,
+            database: self.database,
+            snapshot: Some(path.to_string()),
+            mapped_snapshot: self.mapped_snapshot,
+        }
+    }
+
+    pub fn with_mapped_snapshot(self, path: &str) -> Self {
+        Self {
+            buffer: self.buffer,
+            database: self.database,
+            snapshot: self.snapshot,
+            mapped_snapshot: Some(path.to_string()),
         }
     }
 }
@@ -1,14 +1,14 @@
 use builder::Builder;
 
 use crate::{
-    bloom_filters::BloomFilter,
+    bloom_filters::{ApproximateMembership, BloomFilterError},
     spell_checker::{StorageService, StorageServiceError},
 };
 
 mod builder;
 
 pub struct PasswordDetector {
-    buffer: BloomFilter,
+    buffer: Box<dyn ApproximateMembership>,
     database: Box<dyn StorageService>,
 }
 
@@ -16,6 +16,7 @@ pub struct PasswordDetector {
 pub enum DetectError {
     Initialize(String),
     Storage(StorageServiceError),
+    Buffer(BloomFilterError),
     Dismiss,
     Approve,
 }
@@ -24,7 +25,11 @@ impl PasswordDetector {
     pub fn builder() -> Builder {
         Builder::default()
     }
-    
+
+    pub fn save_buffer(&self, path: &str) -> Result<(), DetectError> {
+        self.buffer.save(path).map_err(DetectError::Buffer)
+    }
+
     pub fn verify(&self, password: &str) -> DetectError {
         match self.buffer.contains(password) {
             true => match self.database.contains(password) {
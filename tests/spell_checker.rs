@@ -0,0 +1,112 @@
+use std::{collections::HashSet, sync::Mutex};
+
+use spell_checker_bloom_filters::{
+    bloom_filters::CuckooFilter,
+    spell_checker::{SpellChecker, SpellCheckerError, StorageService, StorageServiceError},
+    weak_password_detector::{DetectError, PasswordDetector},
+};
+
+#[derive(Default)]
+struct MemoryStorage(Mutex<HashSet<String>>);
+
+impl MemoryStorage {
+    fn from(entries: &[&str]) -> Self {
+        Self(Mutex::new(
+            entries.iter().map(|entry| entry.to_string()).collect(),
+        ))
+    }
+}
+
+impl StorageService for MemoryStorage {
+    fn contains(&self, entry: &str) -> Result<bool, StorageServiceError> {
+        Ok(self.0.lock().unwrap().contains(entry))
+    }
+
+    fn save(&self, entry: String) -> Result<(), StorageServiceError> {
+        self.0.lock().unwrap().insert(entry);
+        Ok(())
+    }
+
+    fn save_bulk(&self, entries: Vec<String>) -> Result<(), StorageServiceError> {
+        self.0.lock().unwrap().extend(entries);
+        Ok(())
+    }
+
+    fn retrieve_all(&self) -> Result<Vec<String>, StorageServiceError> {
+        Ok(self.0.lock().unwrap().iter().cloned().collect())
+    }
+}
+
+#[test]
+fn should_check_spelling_with_cuckoo_buffer() {
+    let buffer: CuckooFilter = CuckooFilter::builder()
+        .with_max_size(1_000)
+        .build()
+        .unwrap();
+
+    let spell_checker = SpellChecker::builder()
+        .with_buffer(Box::new(buffer))
+        .with_database(Box::new(MemoryStorage::from(&["hello", "world"])))
+        .build()
+        .unwrap();
+
+    assert!(spell_checker.check_spelling_of("hello").unwrap());
+    assert!(spell_checker.check_spelling_of("world").unwrap());
+    assert!(!spell_checker.check_spelling_of("helo").unwrap());
+}
+
+#[test]
+fn should_detect_weak_passwords_with_cuckoo_buffer() {
+    let buffer: CuckooFilter = CuckooFilter::builder()
+        .with_max_size(1_000)
+        .build()
+        .unwrap();
+    buffer.insert("123456").unwrap();
+
+    let detector = PasswordDetector::builder()
+        .with_buffer(Box::new(buffer))
+        .with_database(Box::new(MemoryStorage::from(&["123456"])))
+        .build()
+        .unwrap();
+
+    assert!(matches!(detector.verify("123456"), DetectError::Dismiss));
+    assert!(matches!(
+        detector.verify("correct horse battery staple"),
+        DetectError::Approve
+    ));
+}
+
+#[test]
+fn should_return_err_when_buffer_comes_from_many_sources() {
+    let buffer: CuckooFilter = CuckooFilter::builder()
+        .with_max_size(1_000)
+        .build()
+        .unwrap();
+
+    let spell_checker = SpellChecker::builder()
+        .with_buffer(Box::new(buffer))
+        .with_snapshot("missing-snapshot.bin")
+        .with_database(Box::new(MemoryStorage::default()))
+        .build();
+    assert!(matches!(
+        spell_checker,
+        Err(SpellCheckerError::Initialization(_))
+    ));
+
+    let spell_checker = SpellChecker::builder()
+        .with_snapshot("missing-snapshot.bin")
+        .with_static_buffer(8)
+        .with_database(Box::new(MemoryStorage::default()))
+        .build();
+    assert!(matches!(
+        spell_checker,
+        Err(SpellCheckerError::Initialization(_))
+    ));
+
+    let detector = PasswordDetector::builder()
+        .with_snapshot("missing-snapshot.bin")
+        .with_mapped_snapshot("missing-snapshot.bin")
+        .with_database(Box::new(MemoryStorage::default()))
+        .build();
+    assert!(matches!(detector, Err(DetectError::Initialize(_))));
+}